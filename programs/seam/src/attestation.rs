@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+
+use crate::{ErrorCode, PriceAttestation, PricePublisher};
+
+/// Domain tag prefixed to every signed price payload
+pub const ATTESTATION_DOMAIN: &[u8] = b"CADEN_PRICE_V1";
+/// Attestations may be timestamped at most this far ahead of the cluster clock
+pub const MAX_CLOCK_SKEW_SECS: i64 = 30;
+
+// Ed25519 program instruction layout
+const SIGNATURE_OFFSETS_START: usize = 2;
+const SIGNATURE_OFFSETS_LEN: usize = 14;
+const PUBKEY_LEN: usize = 32;
+
+/// Bytes the publisher signs: domain tag, program id, then the Borsh-encoded attestation
+pub fn attestation_message(attestation: &PriceAttestation) -> Result<Vec<u8>> {
+    let mut message = Vec::with_capacity(ATTESTATION_DOMAIN.len() + 32 + 64);
+    message.extend_from_slice(ATTESTATION_DOMAIN);
+    message.extend_from_slice(crate::ID.as_ref());
+    attestation.serialize(&mut message)?;
+    Ok(message)
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Result<u16> {
+    let bytes = data
+        .get(offset..offset + 2)
+        .ok_or(ErrorCode::InvalidAttestationSignature)?;
+    Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+/// Check that the instruction immediately before this one is an Ed25519 program
+/// instruction verifying exactly one signature by `publisher` over `message`.
+/// The Ed25519 program itself fails the transaction if the signature is invalid.
+pub fn verify_ed25519_signature(
+    instructions_sysvar: &AccountInfo,
+    publisher: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    require!(current_index > 0, ErrorCode::InvalidAttestationSignature);
+
+    let ed25519_ix = load_instruction_at_checked(current_index as usize - 1, instructions_sysvar)?;
+    require_keys_eq!(ed25519_ix.program_id, ed25519_program::ID, ErrorCode::InvalidAttestationSignature);
+
+    let data = &ed25519_ix.data;
+    require!(
+        data.len() >= SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_LEN && data[0] == 1,
+        ErrorCode::InvalidAttestationSignature
+    );
+
+    let offsets = SIGNATURE_OFFSETS_START;
+    let signature_ix_index = read_u16(data, offsets + 2)?;
+    let pubkey_offset = read_u16(data, offsets + 4)? as usize;
+    let pubkey_ix_index = read_u16(data, offsets + 6)?;
+    let message_offset = read_u16(data, offsets + 8)? as usize;
+    let message_size = read_u16(data, offsets + 10)? as usize;
+    let message_ix_index = read_u16(data, offsets + 12)?;
+
+    // Signature, key and message must all live inside the Ed25519 instruction itself
+    require!(
+        signature_ix_index == u16::MAX && pubkey_ix_index == u16::MAX && message_ix_index == u16::MAX,
+        ErrorCode::InvalidAttestationSignature
+    );
+
+    let signed_pubkey = data
+        .get(pubkey_offset..pubkey_offset + PUBKEY_LEN)
+        .ok_or(ErrorCode::InvalidAttestationSignature)?;
+    require!(signed_pubkey == publisher.as_ref(), ErrorCode::InvalidAttestationSignature);
+
+    let signed_message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(ErrorCode::InvalidAttestationSignature)?;
+    require!(signed_message == message, ErrorCode::InvalidAttestationSignature);
+
+    Ok(())
+}
+
+/// Enforce the publisher's asset scope, replay protection and staleness, then advance the publisher's watermark
+pub fn check_and_consume_attestation(
+    publisher: &mut PricePublisher,
+    attestation: &PriceAttestation,
+    now: i64,
+) -> Result<()> {
+    require!(publisher.is_active, ErrorCode::PublisherInactive);
+    require!(attestation.source == publisher.source, ErrorCode::InvalidPriceSource);
+    require!(
+        publisher.asset_type_mask & (1 << attestation.asset_type as u8) != 0,
+        ErrorCode::AttestationOutOfScope
+    );
+    require!(
+        publisher.asset_symbols.is_empty() || publisher.asset_symbols.contains(&attestation.asset_symbol),
+        ErrorCode::AttestationOutOfScope
+    );
+
+    // Nonces strictly increase, timestamps never go backwards
+    require!(attestation.nonce > publisher.last_nonce, ErrorCode::AttestationReplayed);
+    require!(attestation.timestamp >= publisher.last_timestamp, ErrorCode::AttestationReplayed);
+
+    require!(attestation.timestamp <= now + MAX_CLOCK_SKEW_SECS, ErrorCode::InvalidAttestationTimestamp);
+    require!(
+        now - attestation.timestamp <= publisher.max_staleness_secs,
+        ErrorCode::StalePrice
+    );
+
+    publisher.last_nonce = attestation.nonce;
+    publisher.last_timestamp = attestation.timestamp;
+    Ok(())
+}
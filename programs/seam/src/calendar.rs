@@ -0,0 +1,212 @@
+use anchor_lang::prelude::*;
+
+use crate::{AssetType, ErrorCode, TradingSchedule};
+
+// Trading calendars, and conversion of T+N business days into unix timestamps.
+// Expiries and deadlines are expressed in unix time; slots are only recorded for reference.
+
+pub const SECONDS_PER_DAY: i64 = 86_400;
+/// Longest T+N, slot lifetime or other day count a deadline may be computed for
+pub const MAX_SETTLEMENT_DAYS: u64 = 365;
+/// Give up looking for the next business day after this many calendar days
+pub const MAX_CALENDAR_SCAN_DAYS: i64 = 400;
+
+// Schedules are evaluated in the exchange's local time
+fn local_day(schedule: &TradingSchedule, timestamp: i64) -> (i64, u16) {
+    let local = timestamp + schedule.utc_offset_secs as i64;
+    (
+        local.div_euclid(SECONDS_PER_DAY),
+        (local.rem_euclid(SECONDS_PER_DAY) / 60) as u16,
+    )
+}
+
+// 1970-01-01 was a Thursday; sessions are indexed Monday = 0
+fn weekday(day: i64) -> usize {
+    (day + 3).rem_euclid(7) as usize
+}
+
+/// Whether `day` (days since the epoch, local time) has a session and is not a holiday
+pub fn is_business_day(schedule: &TradingSchedule, day: i64) -> bool {
+    let session = schedule.sessions[weekday(day)];
+    session.open_minute < session.close_minute && !schedule.holidays.contains(&(day as u32))
+}
+
+/// Whether the underlying market is in session at `timestamp`
+pub fn is_open(schedule: &TradingSchedule, timestamp: i64) -> bool {
+    let (day, minute) = local_day(schedule, timestamp);
+    let session = schedule.sessions[weekday(day)];
+    is_business_day(schedule, day) && minute >= session.open_minute && minute < session.close_minute
+}
+
+/// Read the asset's schedule from its PDA. Instructions always take the derived address,
+/// so a configured schedule cannot be skipped; an empty account means none is set.
+pub fn load_schedule(account: &AccountInfo) -> Result<Option<TradingSchedule>> {
+    if account.data_is_empty() {
+        return Ok(None);
+    }
+    require_keys_eq!(*account.owner, crate::ID, ErrorCode::InvalidTradingSchedule);
+    let data = account.try_borrow_data()?;
+    Ok(Some(TradingSchedule::try_deserialize(&mut &data[..])?))
+}
+
+/// Stock, bond and forex trading must follow a schedule; crypto and commodities
+/// trade around the clock unless a schedule has been configured for them.
+pub fn require_market_open(
+    asset_type: AssetType,
+    schedule: Option<&TradingSchedule>,
+    timestamp: i64,
+) -> Result<()> {
+    match schedule {
+        Some(schedule) => require!(is_open(schedule, timestamp), ErrorCode::MarketClosed),
+        None => require!(
+            !matches!(asset_type, AssetType::Stock | AssetType::Bond | AssetType::Forex),
+            ErrorCode::TradingScheduleRequired
+        ),
+    }
+    Ok(())
+}
+
+/// Unix timestamp at which a T+N settlement falls due for a trade at `timestamp`.
+/// With a schedule this is the session close of the Nth business day after the trade date
+/// (trades after the close roll to the next business day); without one, N whole days.
+/// N is capped at MAX_SETTLEMENT_DAYS.
+pub fn settlement_deadline(
+    schedule: Option<&TradingSchedule>,
+    timestamp: i64,
+    business_days: u64,
+) -> Result<i64> {
+    require!(business_days <= MAX_SETTLEMENT_DAYS, ErrorCode::InvalidSettlementTime);
+    let Some(schedule) = schedule else {
+        return i64::try_from(business_days)
+            .ok()
+            .and_then(|days| days.checked_mul(SECONDS_PER_DAY))
+            .and_then(|secs| timestamp.checked_add(secs))
+            .ok_or_else(|| error!(ErrorCode::MathOverflow));
+    };
+
+    // Trades outside a session belong to the next business day
+    let (mut day, minute) = local_day(schedule, timestamp);
+    if !is_business_day(schedule, day) || minute >= schedule.sessions[weekday(day)].close_minute {
+        day = next_business_day(schedule, day)?;
+    }
+    for _ in 0..business_days {
+        day = next_business_day(schedule, day)?;
+    }
+
+    let close = schedule.sessions[weekday(day)].close_minute as i64 * 60;
+    Ok(day * SECONDS_PER_DAY + close - schedule.utc_offset_secs as i64)
+}
+
+fn next_business_day(schedule: &TradingSchedule, day: i64) -> Result<i64> {
+    (day + 1..=day + MAX_CALENDAR_SCAN_DAYS)
+        .find(|d| is_business_day(schedule, *d))
+        .ok_or_else(|| error!(ErrorCode::InvalidTradingSchedule))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TradingSession;
+
+    // 2024-01-01, a Monday, in days since the epoch
+    const MONDAY: i64 = 19_723;
+    const NEW_YORK_OFFSET: i32 = -5 * 3600;
+
+    // Weekday sessions 09:30-16:00 New York time, closed at weekends
+    fn schedule(holidays: Vec<u32>) -> TradingSchedule {
+        let weekday = TradingSession { open_minute: 9 * 60 + 30, close_minute: 16 * 60 };
+        let mut sessions = [weekday; 7];
+        sessions[5] = TradingSession::default();
+        sessions[6] = TradingSession::default();
+        TradingSchedule {
+            asset_symbol: "AAPL".to_string(),
+            asset_type: AssetType::Stock,
+            utc_offset_secs: NEW_YORK_OFFSET,
+            sessions,
+            holidays,
+            updated_slot: 0,
+            bump: 0,
+        }
+    }
+
+    // Unix timestamp of a local day and time in New York
+    fn at(day: i64, hour: i64, minute: i64) -> i64 {
+        day * SECONDS_PER_DAY + hour * 3600 + minute * 60 - NEW_YORK_OFFSET as i64
+    }
+
+    #[test]
+    fn is_open_follows_the_session_in_local_time() {
+        let schedule = schedule(vec![]);
+        assert!(!is_open(&schedule, at(MONDAY, 9, 29)));
+        assert!(is_open(&schedule, at(MONDAY, 9, 30)));
+        assert!(is_open(&schedule, at(MONDAY, 15, 59)));
+        assert!(!is_open(&schedule, at(MONDAY, 16, 0)));
+        // 10:00 UTC is still before the New York open
+        assert!(!is_open(&schedule, MONDAY * SECONDS_PER_DAY + 10 * 3600));
+    }
+
+    #[test]
+    fn is_open_is_false_at_weekends_and_holidays() {
+        let schedule = schedule(vec![(MONDAY + 1) as u32]);
+        assert!(!is_open(&schedule, at(MONDAY + 1, 12, 0)));
+        assert!(is_open(&schedule, at(MONDAY + 2, 12, 0)));
+        assert!(!is_open(&schedule, at(MONDAY + 5, 12, 0)));
+        assert!(!is_open(&schedule, at(MONDAY + 6, 12, 0)));
+    }
+
+    #[test]
+    fn require_market_open_needs_a_schedule_for_session_traded_assets() {
+        assert!(require_market_open(AssetType::Stock, None, 0).is_err());
+        assert!(require_market_open(AssetType::Crypto, None, 0).is_ok());
+        assert!(require_market_open(AssetType::Crypto, Some(&schedule(vec![])), at(MONDAY + 5, 12, 0)).is_err());
+    }
+
+    #[test]
+    fn settlement_deadline_without_schedule_counts_whole_days() {
+        assert_eq!(settlement_deadline(None, 1_000, 2).unwrap(), 1_000 + 2 * SECONDS_PER_DAY);
+    }
+
+    #[test]
+    fn settlement_deadline_rejects_day_counts_past_the_cap() {
+        let schedule = schedule(vec![]);
+        assert!(settlement_deadline(None, 1_000, MAX_SETTLEMENT_DAYS).is_ok());
+        assert!(settlement_deadline(None, 1_000, MAX_SETTLEMENT_DAYS + 1).is_err());
+        assert!(settlement_deadline(None, 1_000, u64::MAX).is_err());
+        assert!(settlement_deadline(Some(&schedule), at(MONDAY, 10, 0), u64::MAX).is_err());
+        // Overflow on the timestamp itself is reported rather than wrapping
+        assert!(settlement_deadline(None, i64::MAX, 1).is_err());
+    }
+
+    #[test]
+    fn settlement_deadline_lands_on_the_close_of_the_nth_business_day() {
+        let schedule = schedule(vec![]);
+        assert_eq!(settlement_deadline(Some(&schedule), at(MONDAY, 10, 0), 0).unwrap(), at(MONDAY, 16, 0));
+        assert_eq!(settlement_deadline(Some(&schedule), at(MONDAY, 10, 0), 2).unwrap(), at(MONDAY + 2, 16, 0));
+        // Thursday T+2 skips the weekend
+        assert_eq!(settlement_deadline(Some(&schedule), at(MONDAY + 3, 10, 0), 2).unwrap(), at(MONDAY + 7, 16, 0));
+    }
+
+    #[test]
+    fn settlement_deadline_rolls_trades_outside_a_session() {
+        let schedule = schedule(vec![]);
+        // Friday after the close trades as Monday
+        assert_eq!(settlement_deadline(Some(&schedule), at(MONDAY + 4, 17, 0), 1).unwrap(), at(MONDAY + 8, 16, 0));
+        // Saturday trades as Monday
+        assert_eq!(settlement_deadline(Some(&schedule), at(MONDAY + 5, 12, 0), 0).unwrap(), at(MONDAY + 7, 16, 0));
+        // Before Monday's open still belongs to Monday
+        assert_eq!(settlement_deadline(Some(&schedule), at(MONDAY, 8, 0), 0).unwrap(), at(MONDAY, 16, 0));
+    }
+
+    #[test]
+    fn settlement_deadline_skips_holidays() {
+        let schedule = schedule(vec![(MONDAY + 1) as u32]);
+        assert_eq!(settlement_deadline(Some(&schedule), at(MONDAY, 10, 0), 1).unwrap(), at(MONDAY + 2, 16, 0));
+    }
+
+    #[test]
+    fn settlement_deadline_fails_without_any_business_day() {
+        let mut schedule = schedule(vec![]);
+        schedule.sessions = [TradingSession::default(); 7];
+        assert!(settlement_deadline(Some(&schedule), at(MONDAY, 10, 0), 1).is_err());
+    }
+}
@@ -1,10 +1,68 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token, TokenAccount, MintTo, Transfer};
+use anchor_spl::token::{Burn, Mint, Token, TokenAccount, MintTo, Transfer};
 use anchor_spl::associated_token::AssociatedToken;
-use pyth_sdk_solana::load_price_feed_from_account_info;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{self, CloseAccount};
+
+pub mod attestation;
+pub mod calendar;
+pub mod oracle;
+pub mod price_table;
+pub mod slot_nft;
+
+use attestation::{attestation_message, check_and_consume_attestation, verify_ed25519_signature};
+use calendar::{load_schedule, require_market_open, settlement_deadline, MAX_SETTLEMENT_DAYS, SECONDS_PER_DAY};
+use oracle::{
+    authorize_updater, check_price_move, encode_symbol, history_price_at, init_accumulator,
+    load_market_price, load_price, move_limits, oracle_admin, read_pyth_price, read_switchboard_price,
+    record_active_price, record_history, set_move_limits, write_feed_price, write_override_price, MoveLimits,
+};
+use price_table::find_entry;
+use price_table::{find_or_insert, grow_table, load_table_mut, table_space};
+use slot_nft::{
+    burn_slot_token, close_slot_mint, init_slot_metadata, mint_slot_token, refresh_slot_tenor, transfer_slot_token,
+};
 
 declare_id!("3ZstoPk7ho2fAyotF3NTKFjJESr21qAjNXQuVaGSpQ5L");
 
+/// Width of fixed-size asset symbols in zero-copy accounts
+pub const SYMBOL_LEN: usize = 16;
+/// Number of observations kept in each PriceHistory ring buffer
+pub const PRICE_HISTORY_LEN: usize = 256;
+/// Initial number of entries in the zero-copy oracle tables
+pub const DEFAULT_ORACLE_CAPACITY: usize = 32;
+/// Max number of symbols an OracleUpdater or PricePublisher can be scoped to
+pub const MAX_UPDATER_ASSETS: usize = 8;
+/// Pyth oracle program (mainnet-beta and devnet deployments), owner of every Pyth price account
+pub const PYTH_PROGRAM_IDS: [Pubkey; 2] = [
+    pubkey!("FsJ3A3u2vn5cTVofAjvy6y5kwABJAqYWpe4975bi2epH"),
+    pubkey!("gSbePebfvPy7tRqimPoVecS2UsBvYv46ynrzWocc92s"),
+];
+/// Switchboard v2 program, owner of every Switchboard aggregator account
+pub const SWITCHBOARD_PROGRAM_ID: Pubkey = pubkey!("SW1TCH7qEPTdLsDHRgPuMQjbQxKdH2aBStViMFnt64f");
+/// Oldest Pyth or Switchboard price accepted when pulling into the aggregator
+pub const MAX_PULLED_PRICE_AGE_SECS: u64 = 60;
+/// Default max price move per oracle update (5%)
+pub const DEFAULT_MAX_MOVE_BPS_PER_UPDATE: u16 = 500;
+/// Default delay before a single-authority price override can execute (1 hour)
+pub const DEFAULT_OVERRIDE_DELAY_SECS: u64 = 3_600;
+/// Max number of holidays stored in a TradingSchedule
+pub const MAX_HOLIDAYS: usize = 32;
+/// Number of observations kept in each SpreadHeatmap ring buffer
+pub const SPREAD_HISTORY_LEN: usize = 100;
+/// Asset rows in the TenorHeatmap hash table; the account must fit the 10KB CPI allocation limit
+pub const MAX_HEATMAP_ASSETS: usize = 24;
+/// Settlement tenors tracked per asset (T+0 through T+7)
+pub const HEATMAP_TENORS: usize = 8;
+/// LP tokens permanently locked on a pool's first deposit
+pub const MINIMUM_POOL_LIQUIDITY: u64 = 1_000;
+/// Max number of settlement slot pools listed in the PoolRegistry
+pub const MAX_REGISTERED_POOLS: usize = 64;
+/// Upper bound on the protocol royalty charged on secondary slot sales (10%)
+pub const MAX_SLOT_ROYALTY_BPS: u16 = 1000;
+/// Lamports paid from a swept slot's rent to the cranker that expires it
+pub const SLOT_EXPIRY_CRANK_REWARD: u64 = 100_000;
+
 #[program]
 pub mod caden {
     use super::*;
@@ -15,41 +73,106 @@ pub mod caden {
     }
 
     /// Initialize a new market for CFD trading
-    pub fn init_market(ctx: Context<InitMarket>) -> Result<()> {
+    pub fn init_market(
+        ctx: Context<InitMarket>,
+        asset_symbol: String,
+        asset_type: AssetType,
+        entry_price_mode: PriceMode,
+        liquidation_price_mode: PriceMode,
+        settlement_price_mode: PriceMode,
+        settlement_days: u64,
+    ) -> Result<()> {
         let market = &mut ctx.accounts.market;
-        let oracle = &ctx.accounts.oracle_mock;
         let clock = Clock::get()?;
         
+        require!(asset_symbol.len() <= 10, ErrorCode::InvalidAssetSymbol);
+        require!(settlement_days <= 365, ErrorCode::InvalidSettlementTime);
+        
+        // Entry prices are only live while the underlying market is in session
+        let trading_schedule = load_schedule(&ctx.accounts.trading_schedule)?;
+        require_market_open(asset_type, trading_schedule.as_ref(), clock.unix_timestamp)?;
+        
+        // Read the entry price (spot or TWAP/EMA) from the configured oracle source
+        let entry_price = load_market_price(
+            &ctx.accounts.oracle_config,
+            &ctx.accounts.price_account,
+            &ctx.accounts.price_accumulator,
+            &asset_symbol,
+            asset_type,
+            entry_price_mode,
+        )?;
+        
+        market.asset_symbol = asset_symbol;
+        market.asset_type = asset_type;
+        market.entry_price_mode = entry_price_mode;
+        market.liquidation_price_mode = liquidation_price_mode;
+        market.settlement_price_mode = settlement_price_mode;
         // T+0 price = current oracle price
-        market.t0_price = oracle.price;
-        // T+2 price = T+0 price (will be updated once after 2 days simulated)
-        market.t2_price = oracle.price;
-        // expiry slot = clock.slot + 172_800 (≈ 48 h dev-net slots)
-        market.expiry_slot = clock.slot + 172_800;
+        market.t0_price = entry_price;
+        // T+2 price = T+0 price (set by finalize_market_price at expiry)
+        market.t2_price = entry_price;
+        // T+N counted in business days of the underlying's trading calendar
+        market.settlement_days = settlement_days;
+        market.opened_slot = clock.slot;
+        market.expiry_timestamp = settlement_deadline(trading_schedule.as_ref(), clock.unix_timestamp, settlement_days)?;
         // status = Active - ALWAYS SET TO ACTIVE (even if account exists)
         market.status = MarketStatus::Active;
         market.usdc_vault = ctx.accounts.usdc_vault.key();
         market.bump = ctx.bumps.market;
         
-        msg!("Market initialized/updated with T+0 price: {}, expiry slot: {}, status: Active", market.t0_price, market.expiry_slot);
+        msg!("Market initialized/updated with T+0 price: {}, settles T+{} at {}, status: Active",
+             market.t0_price, settlement_days, market.expiry_timestamp);
         Ok(())
     }
 
-    /// Update oracle price (admin only)
-    pub fn update_oracle(ctx: Context<UpdateOracle>, price: u64) -> Result<()> {
-        let oracle = &mut ctx.accounts.oracle_mock;
+    /// Update oracle price (admin or registered updater). The mock carries a single price for
+    /// the asset it was initialized with; `asset_symbol` must name that asset.
+    pub fn update_oracle(
+        ctx: Context<UpdateOracle>,
+        asset_symbol: String,
+        asset_type: AssetType,
+        price: u64,
+    ) -> Result<()> {
         let clock = Clock::get()?;
         
-        // Validate admin
-        require_keys_eq!(oracle.admin, ctx.accounts.admin.key(), ErrorCode::Unauthorized);
+        require!(asset_symbol.len() <= 10, ErrorCode::InvalidAssetSymbol);
+        require!(
+            ctx.accounts.oracle_mock.asset_symbol == asset_symbol && ctx.accounts.oracle_mock.asset_type == asset_type,
+            ErrorCode::AssetNotFound
+        );
+        
+        // Validate admin or scoped updater
+        authorize_updater(
+            &ctx.accounts.oracle_mock.to_account_info(),
+            &ctx.accounts.updater.key(),
+            ctx.accounts.updater_role.as_deref(),
+            Some((&asset_symbol, asset_type)),
+        )?;
         
-        // Price limit removed for testing - allow any price update
+        let oracle = &mut ctx.accounts.oracle_mock;
+        check_price_move(
+            MoveLimits {
+                max_move_bps_per_update: oracle.max_move_bps_per_update,
+                max_move_bps_per_slot: oracle.max_move_bps_per_slot,
+                override_delay_secs: oracle.override_delay_secs,
+            },
+            oracle.price,
+            oracle.updated_slot,
+            price,
+            clock.slot,
+        )?;
         
         // Overwrite OracleMock.price & updated_slot
         oracle.price = price;
         oracle.updated_slot = clock.slot;
+        oracle.updated_by = ctx.accounts.updater.key();
         
-        msg!("Oracle price updated to: {} by admin: {:?}", price, ctx.accounts.admin.key());
+        let accumulator = &mut ctx.accounts.price_accumulator;
+        init_accumulator(accumulator, &asset_symbol, asset_type, ctx.bumps.price_accumulator);
+        record_active_price(accumulator, &ctx.accounts.oracle_config, &[(OracleMode::Mock, price)], clock.slot);
+        record_history(&mut *ctx.accounts.price_history.load_mut()?, clock.slot, price, 0, PriceSource::Manual);
+        
+        msg!("Oracle price updated to: {} by: {:?}", price, ctx.accounts.updater.key());
         Ok(())
     }
 
@@ -65,6 +188,11 @@ pub mod caden {
         
         // Validate market is active
         require!(market.status == MarketStatus::Active, ErrorCode::MarketNotActive);
+        require_market_open(
+            market.asset_type,
+            load_schedule(&ctx.accounts.trading_schedule)?.as_ref(),
+            Clock::get()?.unix_timestamp,
+        )?;
         
         // Validate leverage (1-3x only)
         require!(leverage >= 1 && leverage <= 3, ErrorCode::InvalidLeverage);
@@ -128,14 +256,20 @@ pub mod caden {
     pub fn liquidate_position(ctx: Context<LiquidatePosition>) -> Result<()> {
         let position = &mut ctx.accounts.position;
         let market = &ctx.accounts.market;
-        let oracle = &ctx.accounts.oracle_mock;
         let clock = Clock::get()?;
         
         // Check position is not already liquidated
         require!(!position.liquidated, ErrorCode::PositionAlreadyLiquidated);
         
         // Calculate current P&L
-        let current_price = oracle.price;
+        let current_price = load_market_price(
+            &ctx.accounts.oracle_config,
+            &ctx.accounts.price_account,
+            &ctx.accounts.price_accumulator,
+            &market.asset_symbol,
+            market.asset_type,
+            market.liquidation_price_mode,
+        )?;
         let price_diff = if current_price > position.entry_price {
             current_price - position.entry_price
         } else {
@@ -255,63 +389,489 @@ pub mod caden {
         Ok(())
     }
 
-    /// Initialize oracle mock (admin only)
-    pub fn init_oracle(ctx: Context<InitOracle>) -> Result<()> {
+    /// Initialize oracle mock (admin only). The mock prices a single asset.
+    pub fn init_oracle(ctx: Context<InitOracle>, asset_symbol: String, asset_type: AssetType) -> Result<()> {
+        require!(asset_symbol.len() <= 10, ErrorCode::InvalidAssetSymbol);
+        
         let oracle = &mut ctx.accounts.oracle_mock;
         oracle.admin = ctx.accounts.admin.key();
+        oracle.asset_symbol = asset_symbol;
+        oracle.asset_type = asset_type;
         oracle.price = 50000; // Initialize with default BTC price
         oracle.updated_slot = Clock::get()?.slot;
+        oracle.updated_by = ctx.accounts.admin.key();
+        oracle.max_move_bps_per_update = DEFAULT_MAX_MOVE_BPS_PER_UPDATE;
+        oracle.max_move_bps_per_slot = 0;
+        oracle.override_delay_secs = DEFAULT_OVERRIDE_DELAY_SECS;
+        
+        msg!("Oracle mock for {} initialized by admin: {:?}", oracle.asset_symbol, ctx.accounts.admin.key());
+        Ok(())
+    }
+
+    /// Initialize the oracle configuration that selects which price source drives trading
+    pub fn init_oracle_config(
+        ctx: Context<InitOracleConfig>,
+        mode: OracleMode,
+        max_staleness_slots: u64,
+    ) -> Result<()> {
+        oracle::validate_oracle_mode(mode)?;
+        
+        let config = &mut ctx.accounts.oracle_config;
+        config.admin = ctx.accounts.admin.key();
+        config.mode = mode;
+        config.max_staleness_slots = max_staleness_slots;
+        config.bump = ctx.bumps.oracle_config;
+        
+        msg!("Oracle config initialized: mode {:?}, max staleness {} slots", mode, max_staleness_slots);
+        Ok(())
+    }
+
+    /// Switch the active price source (admin only)
+    pub fn set_oracle_config(
+        ctx: Context<SetOracleConfig>,
+        mode: OracleMode,
+        max_staleness_slots: u64,
+    ) -> Result<()> {
+        oracle::validate_oracle_mode(mode)?;
+        
+        let config = &mut ctx.accounts.oracle_config;
+        config.mode = mode;
+        config.max_staleness_slots = max_staleness_slots;
+        
+        msg!("Oracle config updated: mode {:?}, max staleness {} slots", mode, max_staleness_slots);
+        Ok(())
+    }
+
+    /// Register or rescope a price updater for an oracle account (oracle admin or governance)
+    pub fn add_oracle_updater(
+        ctx: Context<AddOracleUpdater>,
+        updater: Pubkey,
+        asset_type_mask: u8,
+        asset_symbols: Vec<String>,
+    ) -> Result<()> {
+        authorize_oracle_authority(
+            &ctx.accounts.oracle,
+            &ctx.accounts.authority.key(),
+            ctx.accounts.governance.as_deref(),
+        )?;
+        require!(asset_type_mask != 0, ErrorCode::UpdaterOutOfScope);
+        require!(asset_symbols.len() <= MAX_UPDATER_ASSETS, ErrorCode::TooManyUpdaterAssets);
+        require!(asset_symbols.iter().all(|s| s.len() <= 10), ErrorCode::InvalidAssetSymbol);
+        
+        let updater_role = &mut ctx.accounts.updater_role;
+        updater_role.oracle = ctx.accounts.oracle.key();
+        updater_role.updater = updater;
+        updater_role.asset_type_mask = asset_type_mask;
+        updater_role.asset_symbols = asset_symbols;
+        updater_role.added_by = ctx.accounts.authority.key();
+        updater_role.added_slot = Clock::get()?.slot;
+        updater_role.bump = ctx.bumps.updater_role;
+        
+        msg!("Oracle updater {:?} registered for {:?} (asset types: {:#07b}, symbols: {:?})",
+             updater, updater_role.oracle, asset_type_mask, updater_role.asset_symbols);
+        Ok(())
+    }
+
+    /// Remove a price updater from an oracle account (oracle admin or governance)
+    pub fn remove_oracle_updater(ctx: Context<RemoveOracleUpdater>) -> Result<()> {
+        authorize_oracle_authority(
+            &ctx.accounts.oracle,
+            &ctx.accounts.authority.key(),
+            ctx.accounts.governance.as_deref(),
+        )?;
+        
+        msg!("Oracle updater {:?} removed from {:?}",
+             ctx.accounts.updater_role.updater, ctx.accounts.oracle.key());
+        Ok(())
+    }
+
+    /// Configure how far pushed prices may move on an oracle account (governance admin only)
+    pub fn set_oracle_move_limits(
+        ctx: Context<SetOracleMoveLimits>,
+        max_move_bps_per_update: u16,
+        max_move_bps_per_slot: u16,
+        override_delay_secs: u64,
+    ) -> Result<()> {
+        // Loosening the limits must not be in reach of the hot oracle admin key
+        require_keys_eq!(ctx.accounts.governance.admin, ctx.accounts.authority.key(), ErrorCode::Unauthorized);
+        
+        set_move_limits(
+            &ctx.accounts.oracle,
+            MoveLimits {
+                max_move_bps_per_update,
+                max_move_bps_per_slot,
+                override_delay_secs,
+            },
+        )?;
+        
+        msg!("Oracle move limits for {:?}: {} bps/update, {} bps/slot, override delay {}s",
+             ctx.accounts.oracle.key(), max_move_bps_per_update, max_move_bps_per_slot, override_delay_secs);
+        Ok(())
+    }
+
+    /// Propose a price that bypasses the oracle's move limits (oracle admin or governance).
+    /// It executes once a second key holding the other role co-approves, or after the
+    /// oracle's override delay. A key holding both roles only counts as the proposer.
+    pub fn propose_price_override(
+        ctx: Context<ProposePriceOverride>,
+        asset_symbol: String,
+        asset_type: AssetType,
+        price: u64,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        let authority = ctx.accounts.authority.key();
+        
+        require!(asset_symbol.len() <= 10, ErrorCode::InvalidAssetSymbol);
+        require!(price > 0, ErrorCode::InvalidPriceData);
+        
+        let is_admin = oracle_admin(&ctx.accounts.oracle)? == authority;
+        let is_governance = ctx.accounts.governance.as_ref().is_some_and(|g| g.admin == authority);
+        require!(is_admin || is_governance, ErrorCode::Unauthorized);
+        
+        let limits = move_limits(&ctx.accounts.oracle)?;
+        
+        let price_override = &mut ctx.accounts.price_override;
+        price_override.oracle = ctx.accounts.oracle.key();
+        price_override.asset_symbol = asset_symbol;
+        price_override.asset_type = asset_type;
+        price_override.price = price;
+        price_override.proposer = authority;
+        price_override.proposed_slot = clock.slot;
+        price_override.executable_timestamp = clock.unix_timestamp
+            .saturating_add(i64::try_from(limits.override_delay_secs).map_err(|_| ErrorCode::MathOverflow)?);
+        // Record the proposer's approval only, under one role
+        price_override.admin_approved = is_admin;
+        price_override.governance_approved = is_governance && !is_admin;
+        price_override.bump = ctx.bumps.price_override;
+        
+        msg!("Price override proposed for {} ({:?}) = {} on {:?}, executable at {}",
+             price_override.asset_symbol, asset_type, price, price_override.oracle, price_override.executable_timestamp);
+        Ok(())
+    }
+
+    /// Co-approve a pending price override (oracle admin or governance, other than the proposer)
+    pub fn approve_price_override(ctx: Context<ApprovePriceOverride>) -> Result<()> {
+        let authority = ctx.accounts.authority.key();
+        let is_admin = oracle_admin(&ctx.accounts.oracle)? == authority;
+        let is_governance = ctx.accounts.governance.as_ref().is_some_and(|g| g.admin == authority);
+        require!(is_admin || is_governance, ErrorCode::Unauthorized);
+        require_keys_neq!(authority, ctx.accounts.price_override.proposer, ErrorCode::Unauthorized);
+        
+        let price_override = &mut ctx.accounts.price_override;
+        price_override.admin_approved |= is_admin;
+        price_override.governance_approved |= is_governance;
+        
+        msg!("Price override for {} approved by {:?}", price_override.asset_symbol, authority);
+        Ok(())
+    }
+
+    /// Write an approved price override into the oracle (permissionless once ready)
+    pub fn execute_price_override(ctx: Context<ExecutePriceOverride>) -> Result<()> {
+        let clock = Clock::get()?;
+        let price_override = &ctx.accounts.price_override;
         
-        msg!("Oracle mock initialized by admin: {:?}", ctx.accounts.admin.key());
+        let co_approved = price_override.admin_approved && price_override.governance_approved;
+        require!(
+            co_approved || clock.unix_timestamp >= price_override.executable_timestamp,
+            ErrorCode::PriceOverrideNotReady
+        );
+        
+        let (written_mode, recorded_price) = write_override_price(
+            &ctx.accounts.oracle,
+            &price_override.asset_symbol,
+            price_override.asset_type,
+            price_override.price,
+            clock.slot,
+            price_override.proposer,
+        )?;
+        
+        let accumulator = &mut ctx.accounts.price_accumulator;
+        init_accumulator(accumulator, &price_override.asset_symbol, price_override.asset_type, ctx.bumps.price_accumulator);
+        record_active_price(accumulator, &ctx.accounts.oracle_config, &[(written_mode, recorded_price)], clock.slot);
+        record_history(&mut *ctx.accounts.price_history.load_mut()?, clock.slot, price_override.price, 0, PriceSource::Manual);
+        
+        msg!("Price override executed: {} ({:?}) = {} on {:?}",
+             price_override.asset_symbol, price_override.asset_type, price_override.price, price_override.oracle);
+        Ok(())
+    }
+
+    /// Withdraw a pending price override (oracle admin or governance)
+    pub fn cancel_price_override(ctx: Context<CancelPriceOverride>) -> Result<()> {
+        authorize_oracle_authority(
+            &ctx.accounts.oracle,
+            &ctx.accounts.authority.key(),
+            ctx.accounts.governance.as_deref(),
+        )?;
+        
+        msg!("Price override for {} cancelled by {:?}",
+             ctx.accounts.price_override.asset_symbol, ctx.accounts.authority.key());
+        Ok(())
+    }
+
+    /// Create or reconfigure the TWAP/EMA accumulator for an asset (oracle admin only)
+    pub fn configure_price_accumulator(
+        ctx: Context<ConfigurePriceAccumulator>,
+        asset_symbol: String,
+        asset_type: AssetType,
+        twap_window_slots: u64,
+        ema_period_slots: u64,
+    ) -> Result<()> {
+        require!(asset_symbol.len() <= 10, ErrorCode::InvalidAssetSymbol);
+        require!(twap_window_slots > 0 && ema_period_slots > 0, ErrorCode::InvalidAccumulatorConfig);
+        
+        let accumulator = &mut ctx.accounts.price_accumulator;
+        init_accumulator(accumulator, &asset_symbol, asset_type, ctx.bumps.price_accumulator);
+        accumulator.twap_window_slots = twap_window_slots;
+        accumulator.ema_period_slots = ema_period_slots;
+        
+        msg!("Price accumulator configured: {} ({:?}), TWAP window {} slots, EMA period {} slots",
+             asset_symbol, asset_type, twap_window_slots, ema_period_slots);
+        Ok(())
+    }
+
+    /// Create the price history ring buffer for an asset (oracle admin only)
+    pub fn init_price_history(
+        ctx: Context<InitPriceHistory>,
+        asset_symbol: String,
+        asset_type: AssetType,
+    ) -> Result<()> {
+        require!(asset_symbol.len() <= 10, ErrorCode::InvalidAssetSymbol);
+        
+        let mut price_history = ctx.accounts.price_history.load_init()?;
+        price_history.asset_symbol = encode_symbol(&asset_symbol);
+        price_history.asset_type = asset_type as u8;
+        price_history.head = 0;
+        price_history.len = 0;
+        price_history.bump = ctx.bumps.price_history;
+        
+        msg!("Price history initialized: {} ({:?}), capacity {}", asset_symbol, asset_type, PRICE_HISTORY_LEN);
         Ok(())
     }
 
-    /// Initialize spread heatmap (admin only)
-    pub fn init_heatmap(ctx: Context<InitHeatmap>) -> Result<()> {
-        let heatmap = &mut ctx.accounts.heatmap;
+    /// Return the recorded price at or before `slot` (read-only)
+    pub fn get_price_at_slot(
+        ctx: Context<GetPriceAtSlot>,
+        asset_symbol: String,
+        asset_type: AssetType,
+        slot: u64,
+    ) -> Result<HistoricalPrice> {
+        let price_history = ctx.accounts.price_history.load()?;
+        let entry = history_price_at(&price_history, slot)?;
+        
+        msg!("Price for {} ({:?}) at slot {}: {} (recorded slot {})",
+             asset_symbol, asset_type, slot, entry.price, entry.slot);
+        Ok(HistoricalPrice {
+            slot: entry.slot,
+            price: entry.price,
+            confidence: entry.confidence,
+            source: PriceSource::from_u8(entry.source),
+        })
+    }
+
+    /// Record the T+2 price and settle the market once it has expired (callable by anyone)
+    pub fn finalize_market_price(ctx: Context<FinalizeMarketPrice>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let clock = Clock::get()?;
         
-        // Initialize spreads array with zeros
-        heatmap.spreads = [SpreadEntry { bid: 0, ask: 0 }; 100];
+        require!(market.status == MarketStatus::Active, ErrorCode::MarketAlreadySettled);
+        require!(clock.unix_timestamp >= market.expiry_timestamp, ErrorCode::MarketNotExpired);
+        // Settle against a live price, not one frozen over a weekend or holiday
+        require_market_open(market.asset_type, load_schedule(&ctx.accounts.trading_schedule)?.as_ref(), clock.unix_timestamp)?;
+        
+        market.t2_price = load_market_price(
+            &ctx.accounts.oracle_config,
+            &ctx.accounts.price_account,
+            &ctx.accounts.price_accumulator,
+            &market.asset_symbol,
+            market.asset_type,
+            market.settlement_price_mode,
+        )?;
+        market.status = MarketStatus::Settled;
+        
+        msg!("Market settled at slot {}: T+0 price: {}, T+2 price: {} ({:?})",
+             clock.slot, market.t0_price, market.t2_price, market.settlement_price_mode);
+        Ok(())
+    }
+
+    /// Create or replace the trading calendar for an asset (oracle admin only).
+    /// `sessions` holds one local-time session per weekday, Monday first; minutes from midnight.
+    pub fn set_trading_schedule(
+        ctx: Context<SetTradingSchedule>,
+        asset_symbol: String,
+        asset_type: AssetType,
+        utc_offset_secs: i32,
+        sessions: Vec<TradingSession>,
+        holidays: Vec<u32>,
+    ) -> Result<()> {
+        require!(asset_symbol.len() <= 10, ErrorCode::InvalidAssetSymbol);
+        require!(sessions.len() == 7, ErrorCode::InvalidTradingSchedule);
+        require!(
+            sessions.iter().all(|s| s.open_minute <= s.close_minute && s.close_minute <= 24 * 60),
+            ErrorCode::InvalidTradingSchedule
+        );
+        require!(holidays.len() <= MAX_HOLIDAYS, ErrorCode::InvalidTradingSchedule);
+        require!(utc_offset_secs.abs() <= 14 * 3600, ErrorCode::InvalidTradingSchedule);
+        
+        let trading_schedule = &mut ctx.accounts.trading_schedule;
+        trading_schedule.asset_symbol = asset_symbol;
+        trading_schedule.asset_type = asset_type;
+        trading_schedule.utc_offset_secs = utc_offset_secs;
+        trading_schedule.sessions.copy_from_slice(&sessions);
+        trading_schedule.holidays = holidays;
+        trading_schedule.updated_slot = Clock::get()?.slot;
+        trading_schedule.bump = ctx.bumps.trading_schedule;
+        
+        msg!("Trading schedule set for {} ({:?}): UTC offset {}s, {} holidays",
+             trading_schedule.asset_symbol, asset_type, utc_offset_secs, trading_schedule.holidays.len());
+        Ok(())
+    }
+
+    /// Initialize the spread heatmap for the market's asset (oracle admin only)
+    pub fn init_heatmap(ctx: Context<InitHeatmap>, min_crank_interval_slots: u64) -> Result<()> {
+        let market = &ctx.accounts.market;
+        let mut heatmap = ctx.accounts.heatmap.load_init()?;
+        
+        heatmap.market = market.key();
+        heatmap.asset_symbol = encode_symbol(&market.asset_symbol);
+        heatmap.asset_type = market.asset_type as u8;
+        heatmap.min_crank_interval_slots = min_crank_interval_slots;
+        heatmap.last_crank_slot = 0;
+        heatmap.head = 0;
+        heatmap.len = 0;
         heatmap.bump = ctx.bumps.heatmap;
         
-        msg!("Spread heatmap initialized by admin: {:?}", ctx.accounts.admin.key());
+        msg!("Spread heatmap initialized for {} ({:?}), capacity {}, min crank interval {} slots",
+             market.asset_symbol, market.asset_type, SPREAD_HISTORY_LEN, min_crank_interval_slots);
         Ok(())
     }
 
-    /// Crank instruction: Update heatmap with current spread data (callable by anyone)
+    /// Crank instruction: Record the market's current spread in its asset's heatmap (callable by anyone)
     pub fn crank_heatmap(ctx: Context<CrankHeatmap>) -> Result<()> {
-        let heatmap = &mut ctx.accounts.heatmap;
+        let mut heatmap = ctx.accounts.heatmap.load_mut()?;
         let market = &ctx.accounts.market;
-        let oracle = &ctx.accounts.oracle_mock;
         let clock = Clock::get()?;
         
-        // Calculate spread in basis points: (t2 - t0) / t0 * 10000
-        let t0_price = market.t0_price;
+        // At most one entry per slot, and no more often than the configured interval
+        require!(
+            heatmap.len == 0
+                || (clock.slot > heatmap.last_crank_slot
+                    && clock.slot - heatmap.last_crank_slot >= heatmap.min_crank_interval_slots),
+            ErrorCode::CrankTooSoon
+        );
+        
         let t2_price = market.t2_price;
-        let current_oracle_price = oracle.price;
+        let current_oracle_price = load_price(
+            &ctx.accounts.oracle_config,
+            &ctx.accounts.price_account,
+            &market.asset_symbol,
+            market.asset_type,
+        )?.price;
         
         // Use current oracle price as "live" T+0, market.t2_price as T+2
-        let spread_bps = if t0_price > 0 {
-            let spread_ratio = (t2_price as i128 - current_oracle_price as i128) as f64 / current_oracle_price as f64;
-            (spread_ratio * 10000.0) as i16
+        // Spread in basis points: (t2 - t0) / t0 * 10000
+        let spread_bps = if current_oracle_price > 0 {
+            let spread = (t2_price as i128 - current_oracle_price as i128) * 10_000 / current_oracle_price as i128;
+            spread.clamp(i32::MIN as i128, i32::MAX as i128) as i32
         } else {
             0
         };
         
-        // Create new price point and store in first position (simple overwrite instead of circular buffer)
-        let new_point = SpreadEntry {
+        let head = heatmap.head as usize;
+        heatmap.spreads[head] = SpreadEntry {
+            slot: clock.slot,
+            timestamp: clock.unix_timestamp,
             bid: current_oracle_price,
             ask: t2_price,
+            spread_bps,
+            _padding: [0; 4],
         };
-        
-        // For simplicity, just update the first entry
-        heatmap.spreads[0] = new_point;
+        heatmap.head = ((head + 1) % SPREAD_HISTORY_LEN) as u32;
+        if (heatmap.len as usize) < SPREAD_HISTORY_LEN {
+            heatmap.len += 1;
+        }
+        heatmap.last_crank_slot = clock.slot;
         
         msg!("Heatmap cranked at slot {}: spread {}bps, t0=${}, t2=${}", 
              clock.slot, spread_bps, current_oracle_price, t2_price);
         Ok(())
     }
 
+    /// Initialize the asset x settlement tenor heatmap (oracle admin only)
+    pub fn init_tenor_heatmap(ctx: Context<InitTenorHeatmap>) -> Result<()> {
+        let mut heatmap = ctx.accounts.tenor_heatmap.load_init()?;
+        heatmap.last_crank_slot = 0;
+        heatmap.len = 0;
+        heatmap.bump = ctx.bumps.tenor_heatmap;
+        
+        msg!("Tenor heatmap initialized: {} assets x {} tenors", MAX_HEATMAP_ASSETS, HEATMAP_TENORS);
+        Ok(())
+    }
+
+    /// Crank instruction: Refresh tenor heatmap cells from the settlement slot pools and
+    /// CFD markets passed as remaining accounts (callable by anyone)
+    pub fn crank_tenor_heatmap<'info>(ctx: Context<'_, '_, 'info, 'info, CrankTenorHeatmap<'info>>) -> Result<()> {
+        let clock = Clock::get()?;
+        let heatmap_info = ctx.accounts.tenor_heatmap.to_account_info();
+        let mut heatmap = ctx.accounts.tenor_heatmap.load_mut()?;
+        let heatmap = &mut *heatmap;
+        let mut updated = 0u32;
+        
+        for source in ctx.remaining_accounts {
+            require_keys_neq!(source.key(), heatmap_info.key(), ErrorCode::InvalidHeatmapSource);
+            require_keys_eq!(*source.owner, crate::ID, ErrorCode::InvalidHeatmapSource);
+            let data = source.try_borrow_data()?;
+            
+            if data.starts_with(SettlementSlotPool::DISCRIMINATOR) {
+                let pool = SettlementSlotPool::try_deserialize(&mut &data[..])?;
+                let tenor = pool.settlement_time as usize;
+                if !pool.is_active || pool.token_b_amount == 0 || tenor >= HEATMAP_TENORS {
+                    continue;
+                }
+                let index = find_or_insert(
+                    &mut heatmap.rows,
+                    &mut heatmap.len,
+                    encode_symbol(&pool.asset_symbol),
+                    pool.asset_type as u8,
+                )?;
+                let cell = &mut heatmap.rows[index].cells[tenor];
+                // USDC per slot implied by the pool reserves; one tenor token is one wrapped slot
+                cell.pool_price = pool.token_a_amount / pool.token_b_amount;
+                cell.pool_liquidity = pool.token_a_amount;
+                cell.updated_slot = clock.slot;
+                cell.updated_timestamp = clock.unix_timestamp;
+            } else if data.starts_with(Market::DISCRIMINATOR) {
+                let market = Market::try_deserialize(&mut &data[..])?;
+                let tenor = market.settlement_days as usize;
+                if tenor >= HEATMAP_TENORS {
+                    continue;
+                }
+                let index = find_or_insert(
+                    &mut heatmap.rows,
+                    &mut heatmap.len,
+                    encode_symbol(&market.asset_symbol),
+                    market.asset_type as u8,
+                )?;
+                // An active market only knows its T+0 entry price; t2_price is just a copy of it
+                // until the market settles, after which it is the realized T+N price
+                let (tenor, price) = match market.status {
+                    MarketStatus::Active => (0, market.t0_price),
+                    MarketStatus::Settled => (tenor, market.t2_price),
+                };
+                let cell = &mut heatmap.rows[index].cells[tenor];
+                cell.market_price = price;
+                cell.updated_slot = clock.slot;
+                cell.updated_timestamp = clock.unix_timestamp;
+            } else {
+                return err!(ErrorCode::InvalidHeatmapSource);
+            }
+            updated += 1;
+        }
+        
+        heatmap.last_crank_slot = clock.slot;
+        msg!("Tenor heatmap cranked at slot {}: {} sources read, {} assets tracked", clock.slot, updated, heatmap.len);
+        Ok(())
+    }
+
     /// Initialize AMM pool for Long/Short CFD trading
     pub fn init_amm_pool(ctx: Context<InitAmmPool>) -> Result<()> {
         let pool = &mut ctx.accounts.pool;
@@ -552,16 +1112,53 @@ pub mod caden {
         Ok(())
     }
 
-    /// Mint a Settlement Slot NFT - Creates a new asset class for settlement timing rights
+    /// Mint a Settlement Slot NFT - Creates a new asset class for settlement timing rights.
+    /// Slots are numbered by a global counter and their PDA is derived from that id alone,
+    /// so the address never depends on who currently holds the slot.
+    /// The minter pays the (asset, tenor) pricing curve's current price in USDC to the
+    /// protocol fee vault; `max_price` bounds what they are willing to pay.
     pub fn mint_settlement_slot_nft(
         ctx: Context<MintSettlementSlotNft>,
         asset_symbol: String,
         asset_type: AssetType,
         settlement_time: u64,
         slot_duration: u64,
-        slot_price: u64,
-        nft_seed: u64, // User-provided seed for deterministic PDA
+        max_price: u64,
     ) -> Result<()> {
+        // A slot must be live for at least a day before anyone pays for it
+        require!((1..=MAX_SETTLEMENT_DAYS).contains(&slot_duration), ErrorCode::InvalidSlotDuration);
+        
+        // Price the slot off the curve and enforce the supply cap
+        let slot_pricing = &mut ctx.accounts.slot_pricing;
+        require!(
+            slot_pricing.max_supply == 0 || slot_supply_committed(slot_pricing) < slot_pricing.max_supply,
+            ErrorCode::SlotSupplyCapReached
+        );
+        let slot_price = slot_curve_price(slot_pricing)?;
+        require!(slot_price <= max_price, ErrorCode::SlippageExceeded);
+        slot_pricing.outstanding += 1;
+        slot_pricing.total_minted += 1;
+        slot_pricing.total_revenue += slot_price;
+        
+        if slot_price > 0 {
+            anchor_spl::token::transfer(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.owner_usdc_account.to_account_info(),
+                        to: ctx.accounts.protocol_fee_vault.to_account_info(),
+                        authority: ctx.accounts.owner.to_account_info(),
+                    },
+                ),
+                slot_price,
+            )?;
+        }
+        
+        let slot_counter = &mut ctx.accounts.slot_counter;
+        let slot_id = slot_counter.next_slot_id;
+        slot_counter.next_slot_id += 1;
+        slot_counter.bump = ctx.bumps.slot_counter;
+        
         let settlement_slot = &mut ctx.accounts.settlement_slot;
         let clock = Clock::get()?;
         
@@ -572,43 +1169,706 @@ pub mod caden {
         // Validate asset type
         require!(matches!(asset_type, AssetType::Stock | AssetType::Crypto | AssetType::Bond | AssetType::Commodity | AssetType::Forex), ErrorCode::InvalidAssetType);
         
-        // Initialize settlement slot NFT
-        settlement_slot.slot_id = nft_seed; // Use user-provided seed as unique ID
-        settlement_slot.asset_symbol = asset_symbol.clone();
-        settlement_slot.asset_type = asset_type;
-        settlement_slot.settlement_time = settlement_time;
-        settlement_slot.slot_duration = slot_duration;
-        settlement_slot.owner = ctx.accounts.owner.key();
-        settlement_slot.mint_price = slot_price;
-        settlement_slot.created_slot = clock.slot;
-        settlement_slot.expiry_slot = clock.slot + (slot_duration * 172_800); // Convert days to slots
-        settlement_slot.is_tradable = true;
-        settlement_slot.is_active = true;
-        settlement_slot.bump = ctx.bumps.settlement_slot;
-        
-        msg!("Settlement Slot NFT minted: Asset: {}, Settlement Time: T+{}, Duration: {} days, Price: ${}", 
-             asset_symbol, settlement_time, slot_duration, slot_price / 1000000);
+        // Initialize settlement slot and issue its NFT
+        let expiry_timestamp =
+            settlement_deadline(load_schedule(&ctx.accounts.trading_schedule)?.as_ref(), clock.unix_timestamp, slot_duration)?;
+        issue_settlement_slot(
+            settlement_slot,
+            SlotTerms {
+                slot_id,
+                asset_symbol: asset_symbol.clone(),
+                asset_type,
+                settlement_time,
+                slot_duration,
+                mint_price: slot_price,
+                expiry_timestamp,
+                notional_budget: ctx.accounts.slot_pricing.notional_budget,
+                creator: ctx.accounts.owner.key(),
+                bump: ctx.bumps.settlement_slot,
+            },
+            &ctx.accounts.slot_mint.to_account_info(),
+            &ctx.accounts.owner_token_account.to_account_info(),
+            &ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.token_2022_program.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        
+        msg!("Settlement Slot NFT minted: ID: {}, Mint: {:?}, Asset: {}, Settlement Time: T+{}, Duration: {} days, Expires: {}, Price: ${}", 
+             slot_id, ctx.accounts.slot_mint.key(), asset_symbol, settlement_time, slot_duration, expiry_timestamp, slot_price / 1000000);
         Ok(())
     }
 
-    /// Trade a Settlement Slot NFT - Enables trading of settlement timing rights
+    /// Trade a Settlement Slot NFT directly between two signers (OTC): the buyer pays the
+    /// agreed USDC price, less any protocol royalty, and receives the slot token in the same transaction
     pub fn trade_settlement_slot(
         ctx: Context<TradeSettlementSlot>,
-        new_price: u64,
+        price: u64,
     ) -> Result<()> {
         let settlement_slot = &mut ctx.accounts.settlement_slot;
         
-        // Validate ownership
-        require_keys_eq!(settlement_slot.owner, ctx.accounts.seller.key(), ErrorCode::Unauthorized);
+        // Ownership is checked by the seller_token_account constraints
+        require!(settlement_slot.is_tradable, ErrorCode::SlotNotTradable);
+        require!(settlement_slot.is_active, ErrorCode::SlotExpired);
+        require!(Clock::get()?.unix_timestamp < settlement_slot.expiry_timestamp, ErrorCode::SlotExpired);
+        settlement_slot.mint_price = price;
+        
+        let royalty = pay_for_slot(
+            &ctx.accounts.token_program,
+            &ctx.accounts.buyer,
+            &ctx.accounts.buyer_usdc_account,
+            &ctx.accounts.seller_usdc_account,
+            &ctx.accounts.protocol_fee_vault,
+            price,
+            ctx.accounts.governance.slot_royalty_bps,
+        )?;
+        
+        // Transfer ownership by moving the slot token
+        transfer_slot_token(
+            &ctx.accounts.token_2022_program.to_account_info(),
+            &ctx.accounts.slot_mint.to_account_info(),
+            &ctx.accounts.seller_token_account.to_account_info(),
+            &ctx.accounts.buyer_token_account.to_account_info(),
+            &ctx.accounts.seller.to_account_info(),
+            &[],
+        )?;
+        
+        msg!("Settlement Slot traded: New owner: {:?}, Price: ${}, Royalty: ${}", 
+             ctx.accounts.buyer.key(), price / 1000000, royalty / 1000000);
+        Ok(())
+    }
+
+    /// List a Settlement Slot for sale at a fixed USDC price. The slot token is held in
+    /// escrow by the listing until it is bought or the listing is cancelled.
+    pub fn list_settlement_slot(
+        ctx: Context<ListSettlementSlot>,
+        price: u64,
+    ) -> Result<()> {
+        let settlement_slot = &ctx.accounts.settlement_slot;
+        
+        require!(price > 0, ErrorCode::InvalidAmount);
         require!(settlement_slot.is_tradable, ErrorCode::SlotNotTradable);
         require!(settlement_slot.is_active, ErrorCode::SlotExpired);
+        require!(Clock::get()?.unix_timestamp < settlement_slot.expiry_timestamp, ErrorCode::SlotExpired);
+        
+        transfer_slot_token(
+            &ctx.accounts.token_2022_program.to_account_info(),
+            &ctx.accounts.slot_mint.to_account_info(),
+            &ctx.accounts.seller_token_account.to_account_info(),
+            &ctx.accounts.escrow_token_account.to_account_info(),
+            &ctx.accounts.seller.to_account_info(),
+            &[],
+        )?;
+        
+        let listing = &mut ctx.accounts.listing;
+        listing.settlement_slot = settlement_slot.key();
+        listing.seller = ctx.accounts.seller.key();
+        listing.price = price;
+        listing.created_timestamp = Clock::get()?.unix_timestamp;
+        listing.bump = ctx.bumps.listing;
+        
+        msg!("Settlement Slot listed: Slot: {:?}, Seller: {:?}, Price: ${}",
+             listing.settlement_slot, listing.seller, price / 1000000);
+        Ok(())
+    }
+
+    /// Buy a listed Settlement Slot: pays the seller (less any protocol royalty) and
+    /// releases the slot token from escrow. `max_price` guards against a relisting race.
+    pub fn buy_settlement_slot(
+        ctx: Context<BuySettlementSlot>,
+        max_price: u64,
+    ) -> Result<()> {
+        let price = ctx.accounts.listing.price;
+        require!(price <= max_price, ErrorCode::SlippageExceeded);
+        require!(ctx.accounts.settlement_slot.is_tradable, ErrorCode::SlotNotTradable);
+        require!(ctx.accounts.settlement_slot.is_active, ErrorCode::SlotExpired);
+        require!(
+            Clock::get()?.unix_timestamp < ctx.accounts.settlement_slot.expiry_timestamp,
+            ErrorCode::SlotExpired
+        );
+        
+        let royalty = pay_for_slot(
+            &ctx.accounts.token_program,
+            &ctx.accounts.buyer,
+            &ctx.accounts.buyer_usdc_account,
+            &ctx.accounts.seller_usdc_account,
+            &ctx.accounts.protocol_fee_vault,
+            price,
+            ctx.accounts.governance.slot_royalty_bps,
+        )?;
+        
+        let listing = &ctx.accounts.listing;
+        release_slot_escrow(
+            &ctx.accounts.token_2022_program,
+            &ctx.accounts.slot_mint,
+            &ctx.accounts.escrow_token_account,
+            &ctx.accounts.buyer_token_account,
+            &listing.to_account_info(),
+            &[b"slot_listing", listing.settlement_slot.as_ref(), &[listing.bump]],
+            &ctx.accounts.seller,
+        )?;
+        ctx.accounts.settlement_slot.mint_price = price;
+        
+        msg!("Settlement Slot bought: Slot: {:?}, Buyer: {:?}, Price: ${}, Royalty: ${}",
+             ctx.accounts.settlement_slot.key(), ctx.accounts.buyer.key(), price / 1000000, royalty / 1000000);
+        Ok(())
+    }
+
+    /// Cancel a listing and return the slot token to the seller
+    pub fn cancel_slot_listing(ctx: Context<CancelSlotListing>) -> Result<()> {
+        let listing = &ctx.accounts.listing;
+        release_slot_escrow(
+            &ctx.accounts.token_2022_program,
+            &ctx.accounts.slot_mint,
+            &ctx.accounts.escrow_token_account,
+            &ctx.accounts.seller_token_account,
+            &listing.to_account_info(),
+            &[b"slot_listing", listing.settlement_slot.as_ref(), &[listing.bump]],
+            &ctx.accounts.seller,
+        )?;
+        
+        msg!("Settlement Slot listing cancelled: Slot: {:?}", ctx.accounts.listing.settlement_slot);
+        Ok(())
+    }
+
+    /// Offer a T+0 Settlement Slot for rent at a USDC fee per day. The slot token moves into an
+    /// escrow owned by the rental, so the lender keeps ownership but cannot sell it while listed.
+    pub fn list_slot_for_rent(
+        ctx: Context<ListSlotForRent>,
+        fee_per_day: u64,
+        max_days: u64,
+    ) -> Result<()> {
+        let settlement_slot = &ctx.accounts.settlement_slot;
+        require!(settlement_slot.settlement_time == 0, ErrorCode::NotInstantSettlement);
+        require!(max_days > 0, ErrorCode::InvalidRentalPeriod);
+        require!(settlement_slot.is_active, ErrorCode::SlotExpired);
+        require!(Clock::get()?.unix_timestamp < settlement_slot.expiry_timestamp, ErrorCode::SlotExpired);
+        
+        transfer_slot_token(
+            &ctx.accounts.token_2022_program.to_account_info(),
+            &ctx.accounts.slot_mint.to_account_info(),
+            &ctx.accounts.lender_token_account.to_account_info(),
+            &ctx.accounts.escrow_token_account.to_account_info(),
+            &ctx.accounts.lender.to_account_info(),
+            &[],
+        )?;
+        
+        let rental = &mut ctx.accounts.rental;
+        rental.settlement_slot = settlement_slot.key();
+        rental.lender = ctx.accounts.lender.key();
+        rental.payment_account = ctx.accounts.lender_usdc_account.key();
+        rental.fee_per_day = fee_per_day;
+        rental.max_days = max_days;
+        rental.renter = Pubkey::default();
+        rental.rented_until = 0;
+        rental.total_earned = 0;
+        rental.bump = ctx.bumps.rental;
+        
+        msg!("Settlement Slot listed for rent: Slot: {:?}, Fee: ${}/day, Max: {} days",
+             rental.settlement_slot, fee_per_day / 1000000, max_days);
+        Ok(())
+    }
+
+    /// Rent a listed slot for `days`, paying the full fee upfront to the lender. The renter
+    /// may use the slot in instant_t0_settlement until the rental expires.
+    pub fn rent_settlement_slot(
+        ctx: Context<RentSettlementSlot>,
+        days: u64,
+        max_fee_per_day: u64,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let rental = &mut ctx.accounts.rental;
+        require!(now >= rental.rented_until, ErrorCode::SlotAlreadyRented);
+        require!(rental.fee_per_day <= max_fee_per_day, ErrorCode::SlippageExceeded);
+        require!(days > 0 && days <= rental.max_days, ErrorCode::InvalidRentalPeriod);
+        
+        let rented_until = now + days as i64 * SECONDS_PER_DAY;
+        require!(rented_until <= ctx.accounts.settlement_slot.expiry_timestamp, ErrorCode::InvalidRentalPeriod);
+        
+        let fee = rental.fee_per_day.checked_mul(days).ok_or(ErrorCode::MathOverflow)?;
+        if fee > 0 {
+            anchor_spl::token::transfer(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.renter_usdc_account.to_account_info(),
+                        to: ctx.accounts.lender_usdc_account.to_account_info(),
+                        authority: ctx.accounts.renter.to_account_info(),
+                    },
+                ),
+                fee,
+            )?;
+        }
+        
+        rental.renter = ctx.accounts.renter.key();
+        rental.rented_until = rented_until;
+        rental.total_earned += fee;
+        
+        msg!("Settlement Slot rented: Slot: {:?}, Renter: {:?}, Until: {}, Fee: ${}",
+             rental.settlement_slot, rental.renter, rented_until, fee / 1000000);
+        Ok(())
+    }
+
+    /// Withdraw a slot from the rental market once any active rental has ended,
+    /// returning the token to the lender
+    pub fn end_slot_rental(ctx: Context<EndSlotRental>) -> Result<()> {
+        let rental = &ctx.accounts.rental;
+        require!(Clock::get()?.unix_timestamp >= rental.rented_until, ErrorCode::SlotAlreadyRented);
+        
+        release_slot_escrow(
+            &ctx.accounts.token_2022_program,
+            &ctx.accounts.slot_mint,
+            &ctx.accounts.escrow_token_account,
+            &ctx.accounts.lender_token_account,
+            &rental.to_account_info(),
+            &[b"slot_rental", rental.settlement_slot.as_ref(), &[rental.bump]],
+            &ctx.accounts.lender,
+        )?;
+        
+        msg!("Settlement Slot rental ended: Slot: {:?}, Earned: ${}",
+             rental.settlement_slot, rental.total_earned / 1000000);
+        Ok(())
+    }
+
+    /// Lock a T+0 slot in a vault and mint `total_fractions` fungible fraction tokens to the holder.
+    /// Each fraction entitles its bearer to settle an equal share of the slot's remaining notional
+    /// budget; fractions are burned as they are used in instant_t0_settlement. If the slot expires
+    /// in the vault, expire_settlement_slot closes the vault and any unused fractions lapse with it.
+    pub fn fractionalize_slot(
+        ctx: Context<FractionalizeSlot>,
+        total_fractions: u64,
+    ) -> Result<()> {
+        let settlement_slot = &ctx.accounts.settlement_slot;
+        require!(settlement_slot.settlement_time == 0, ErrorCode::NotInstantSettlement);
+        require!(settlement_slot.is_active, ErrorCode::SlotExpired);
+        require!(Clock::get()?.unix_timestamp < settlement_slot.expiry_timestamp, ErrorCode::SlotExpired);
+        require!(total_fractions > 0, ErrorCode::InvalidAmount);
+        
+        let remaining_notional = settlement_slot.notional_budget - settlement_slot.notional_used;
+        let notional_per_fraction = remaining_notional / total_fractions;
+        require!(notional_per_fraction > 0, ErrorCode::InvalidAmount);
+        
+        transfer_slot_token(
+            &ctx.accounts.token_2022_program.to_account_info(),
+            &ctx.accounts.slot_mint.to_account_info(),
+            &ctx.accounts.owner_token_account.to_account_info(),
+            &ctx.accounts.escrow_token_account.to_account_info(),
+            &ctx.accounts.owner.to_account_info(),
+            &[],
+        )?;
+        
+        let vault = &mut ctx.accounts.fraction_vault;
+        vault.settlement_slot = settlement_slot.key();
+        vault.owner = ctx.accounts.owner.key();
+        vault.fraction_mint = ctx.accounts.fraction_mint.key();
+        vault.total_fractions = total_fractions;
+        vault.fractions_burned = 0;
+        vault.notional_per_fraction = notional_per_fraction;
+        vault.bump = ctx.bumps.fraction_vault;
+        
+        let seeds = &[
+            b"fraction_vault",
+            vault.settlement_slot.as_ref(),
+            &[vault.bump],
+        ];
+        let signer = &[&seeds[..]];
+        anchor_spl::token::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.fraction_mint.to_account_info(),
+                    to: ctx.accounts.owner_fraction_account.to_account_info(),
+                    authority: vault.to_account_info(),
+                },
+                signer,
+            ),
+            total_fractions,
+        )?;
+        
+        msg!("Settlement Slot fractionalized: Slot: {:?}, Fractions: {}, Notional per fraction: ${}",
+             vault.settlement_slot, total_fractions, notional_per_fraction / 1000000);
+        Ok(())
+    }
+
+    /// Reassemble a fractionalized slot: whoever holds every outstanding fraction burns them
+    /// and receives the slot token back from the vault. Once every fraction has been burned
+    /// only the vault owner may take the slot back. The vault's rent returns to its owner.
+    pub fn redeem_slot(ctx: Context<RedeemSlot>) -> Result<()> {
+        let outstanding = ctx.accounts.fraction_mint.supply;
+        require!(
+            outstanding > 0 || ctx.accounts.redeemer.key() == ctx.accounts.fraction_vault.owner,
+            ErrorCode::Unauthorized
+        );
+        if outstanding > 0 {
+            anchor_spl::token::burn(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Burn {
+                        mint: ctx.accounts.fraction_mint.to_account_info(),
+                        from: ctx.accounts.redeemer_fraction_account.to_account_info(),
+                        authority: ctx.accounts.redeemer.to_account_info(),
+                    },
+                ),
+                outstanding,
+            )?;
+        }
+        
+        let vault = &ctx.accounts.fraction_vault;
+        release_slot_escrow(
+            &ctx.accounts.token_2022_program,
+            &ctx.accounts.slot_mint,
+            &ctx.accounts.escrow_token_account,
+            &ctx.accounts.redeemer_token_account,
+            &vault.to_account_info(),
+            &[b"fraction_vault", vault.settlement_slot.as_ref(), &[vault.bump]],
+            &ctx.accounts.redeemer.to_account_info(),
+        )?;
+        
+        msg!("Settlement Slot redeemed: Slot: {:?}, Redeemer: {:?}, Fractions burned: {}",
+             vault.settlement_slot, ctx.accounts.redeemer.key(), outstanding);
+        Ok(())
+    }
+
+    /// Upgrade a T+N slot to T+0. The holder pays the difference between the current T+0 and
+    /// T+N prices on the asset's pricing curves; the slot moves from the T+N supply to the
+    /// (capped) T+0 supply and takes on the T+0 notional budget.
+    pub fn upgrade_settlement_slot(
+        ctx: Context<UpgradeSettlementSlot>,
+        max_premium: u64,
+    ) -> Result<()> {
+        let settlement_slot = &mut ctx.accounts.settlement_slot;
+        require!(settlement_slot.settlement_time > 0, ErrorCode::InvalidSettlementTime);
+        require!(settlement_slot.is_active, ErrorCode::SlotExpired);
+        require!(Clock::get()?.unix_timestamp < settlement_slot.expiry_timestamp, ErrorCode::SlotExpired);
+        
+        let t0_pricing = &mut ctx.accounts.t0_pricing;
+        require!(slot_supply_committed(t0_pricing) < t0_pricing.max_supply, ErrorCode::SlotSupplyCapReached);
+        let premium = slot_curve_price(t0_pricing)?.saturating_sub(slot_curve_price(&ctx.accounts.tn_pricing)?);
+        require!(premium <= max_premium, ErrorCode::SlippageExceeded);
+        
+        if premium > 0 {
+            anchor_spl::token::transfer(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.owner_usdc_account.to_account_info(),
+                        to: ctx.accounts.protocol_fee_vault.to_account_info(),
+                        authority: ctx.accounts.owner.to_account_info(),
+                    },
+                ),
+                premium,
+            )?;
+        }
+        
+        // Move the slot between supplies
+        t0_pricing.outstanding += 1;
+        t0_pricing.total_revenue += premium;
+        let tn_pricing = &mut ctx.accounts.tn_pricing;
+        tn_pricing.outstanding = tn_pricing.outstanding.saturating_sub(1);
+        
+        let from_tenor = settlement_slot.settlement_time;
+        settlement_slot.settlement_time = 0;
+        settlement_slot.notional_budget = t0_pricing.notional_budget;
+        settlement_slot.notional_used = 0;
+        settlement_slot.mint_price = settlement_slot.mint_price.saturating_add(premium);
+        
+        let seed_bytes = settlement_slot.slot_id.to_le_bytes();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"settlement_slot",
+            &seed_bytes,
+            &[settlement_slot.bump],
+        ]];
+        refresh_slot_tenor(
+            &ctx.accounts.token_2022_program.to_account_info(),
+            &ctx.accounts.slot_mint.to_account_info(),
+            &settlement_slot.to_account_info(),
+            signer_seeds,
+            settlement_slot,
+        )?;
+        
+        emit!(SlotUpgraded {
+            settlement_slot: settlement_slot.key(),
+            owner: ctx.accounts.owner.key(),
+            asset_symbol: settlement_slot.asset_symbol.clone(),
+            from_tenor,
+            to_tenor: 0,
+            premium,
+        });
+        msg!("Settlement Slot upgraded: ID: {}, T+{} -> T+0, Premium: ${}",
+             settlement_slot.slot_id, from_tenor, premium / 1000000);
+        Ok(())
+    }
+
+    /// Sweep a settlement slot that has expired or used up its notional budget (permissionless).
+    /// Burns the slot token from its holder, retires it from the pricing curve's supply, and
+    /// closes the account and its mint: the cranker is paid a small reward and the holder gets
+    /// the rest of the rent. A slot held in escrow by a listing, rental or fraction vault also
+    /// has its escrow closed, refunding the seller, lender or fractionalizing owner.
+    pub fn expire_settlement_slot(ctx: Context<ExpireSettlementSlot>) -> Result<()> {
+        let slot_key = ctx.accounts.settlement_slot.key();
+        let escrow = slot_escrow_holder(&ctx.accounts.holder, &slot_key)?;
+        require_keys_eq!(
+            ctx.accounts.rent_recipient.key(),
+            escrow.map_or(ctx.accounts.holder.key(), |(_, _, beneficiary)| beneficiary),
+            ErrorCode::Unauthorized
+        );
+        
+        let settlement_slot = &mut ctx.accounts.settlement_slot;
+        require!(
+            !settlement_slot.is_active || Clock::get()?.unix_timestamp >= settlement_slot.expiry_timestamp,
+            ErrorCode::SlotNotExpired
+        );
+        settlement_slot.is_active = false;
+        
+        let seed_bytes = settlement_slot.slot_id.to_le_bytes();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"settlement_slot",
+            &seed_bytes,
+            &[settlement_slot.bump],
+        ]];
+        burn_slot_token(
+            &ctx.accounts.token_2022_program.to_account_info(),
+            &ctx.accounts.slot_mint.to_account_info(),
+            &ctx.accounts.holder_token_account.to_account_info(),
+            &settlement_slot.to_account_info(),
+            signer_seeds,
+        )?;
+        close_slot_mint(
+            &ctx.accounts.token_2022_program.to_account_info(),
+            &ctx.accounts.slot_mint.to_account_info(),
+            &settlement_slot.to_account_info(),
+            &ctx.accounts.rent_recipient,
+            signer_seeds,
+        )?;
+        
+        // The escrow's token account is empty after the burn; close it and the escrow itself
+        if let Some((prefix, bump, _)) = escrow {
+            close_slot_escrow(
+                &ctx.accounts.token_2022_program,
+                &ctx.accounts.holder_token_account,
+                &ctx.accounts.holder,
+                &[prefix, slot_key.as_ref(), &[bump]],
+                &ctx.accounts.rent_recipient,
+            )?;
+            close_program_account(&ctx.accounts.holder, &ctx.accounts.rent_recipient)?;
+        }
+        
+        let slot_pricing = &mut ctx.accounts.slot_pricing;
+        slot_pricing.outstanding = slot_pricing.outstanding.saturating_sub(1);
+        
+        // Pay the cranker out of the slot's rent; the close constraint refunds the rest
+        let slot_info = ctx.accounts.settlement_slot.to_account_info();
+        let reward = SLOT_EXPIRY_CRANK_REWARD.min(slot_info.lamports());
+        **slot_info.try_borrow_mut_lamports()? -= reward;
+        **ctx.accounts.cranker.to_account_info().try_borrow_mut_lamports()? += reward;
+        
+        msg!("Settlement Slot expired: ID: {}, Holder: {:?}, Escrowed: {}, Cranker reward: {} lamports",
+             ctx.accounts.settlement_slot.slot_id, ctx.accounts.holder.key(), escrow.is_some(), reward);
+        Ok(())
+    }
+
+    /// Set the protocol royalty on secondary slot sales (governance admin only)
+    pub fn set_slot_royalty(
+        ctx: Context<SetSlotRoyalty>,
+        royalty_bps: u16,
+    ) -> Result<()> {
+        require!(royalty_bps <= MAX_SLOT_ROYALTY_BPS, ErrorCode::InvalidFeeRate);
+        ctx.accounts.governance.slot_royalty_bps = royalty_bps;
+        
+        msg!("Settlement slot royalty set to {} bps", royalty_bps);
+        Ok(())
+    }
+
+    /// Configure the mint pricing curve for an (asset, tenor): each new slot costs
+    /// `base_price + price_step * outstanding`. T+0 slots must have a supply cap and
+    /// a notional budget, which caps how much bet notional each slot can settle instantly.
+    pub fn set_slot_pricing(
+        ctx: Context<SetSlotPricing>,
+        asset_symbol: String,
+        asset_type: AssetType,
+        settlement_time: u64,
+        params: SlotPricingParams,
+    ) -> Result<()> {
+        let SlotPricingParams {
+            base_price,
+            price_step,
+            max_supply,
+            notional_budget,
+        } = params;
+        require!(asset_symbol.len() <= 10, ErrorCode::InvalidAssetSymbol);
+        require!(settlement_time <= 365, ErrorCode::InvalidSettlementTime);
+        require!(settlement_time > 0 || max_supply > 0, ErrorCode::SupplyCapRequired);
+        require!(settlement_time > 0 || notional_budget > 0, ErrorCode::InvalidAmount);
+        
+        let slot_pricing = &mut ctx.accounts.slot_pricing;
+        require!(max_supply == 0 || max_supply >= slot_supply_committed(slot_pricing), ErrorCode::SlotSupplyCapReached);
+        slot_pricing.asset_symbol = asset_symbol.clone();
+        slot_pricing.asset_type = asset_type;
+        slot_pricing.settlement_time = settlement_time;
+        slot_pricing.base_price = base_price;
+        slot_pricing.price_step = price_step;
+        slot_pricing.max_supply = max_supply;
+        slot_pricing.notional_budget = notional_budget;
+        slot_pricing.bump = ctx.bumps.slot_pricing;
+        
+        msg!("Slot pricing for {} T+{}: base ${}, step ${}, cap {}, budget ${}",
+             asset_symbol, settlement_time, base_price / 1000000, price_step / 1000000, max_supply,
+             notional_budget / 1000000);
+        Ok(())
+    }
+
+    /// Open a Dutch auction selling `total_slots` T+0 slots for an asset (governance admin only).
+    /// The auction runs from `start_timestamp` to `end_timestamp` (unix time); the price starts at
+    /// `start_price` and falls by `decay_per_second` down to `floor_price`. The lot is reserved against the asset's T+0 supply cap up front, without
+    /// raising the curve price of primary mints until its slots are actually sold.
+    pub fn create_slot_auction(
+        ctx: Context<CreateSlotAuction>,
+        asset_symbol: String,
+        asset_type: AssetType,
+        epoch: u64,
+        params: SlotAuctionParams,
+    ) -> Result<()> {
+        let SlotAuctionParams {
+            total_slots,
+            slot_duration,
+            start_price,
+            floor_price,
+            decay_per_second,
+            start_timestamp,
+            end_timestamp,
+            notional_budget,
+        } = params;
+        require!(asset_symbol.len() <= 10, ErrorCode::InvalidAssetSymbol);
+        require!((1..=MAX_SETTLEMENT_DAYS).contains(&slot_duration), ErrorCode::InvalidSlotDuration);
+        require!(total_slots > 0 && notional_budget > 0, ErrorCode::InvalidAmount);
+        require!(floor_price <= start_price, ErrorCode::InvalidAmount);
+        require!(start_timestamp < end_timestamp, ErrorCode::InvalidAuctionWindow);
+        
+        // Reserve the lot so primary mints cannot oversell the cap while the auction runs
+        let slot_pricing = &mut ctx.accounts.slot_pricing;
+        let committed = slot_supply_committed(slot_pricing)
+            .checked_add(total_slots)
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(committed <= slot_pricing.max_supply, ErrorCode::SlotSupplyCapReached);
+        slot_pricing.reserved += total_slots;
+        
+        let auction = &mut ctx.accounts.auction;
+        auction.asset_symbol = asset_symbol.clone();
+        auction.asset_type = asset_type;
+        auction.epoch = epoch;
+        auction.total_slots = total_slots;
+        auction.sold = 0;
+        auction.slot_duration = slot_duration;
+        auction.start_price = start_price;
+        auction.floor_price = floor_price;
+        auction.decay_per_second = decay_per_second;
+        auction.start_timestamp = start_timestamp;
+        auction.end_timestamp = end_timestamp;
+        auction.notional_budget = notional_budget;
+        auction.revenue = 0;
+        auction.is_finalized = false;
+        auction.bump = ctx.bumps.auction;
+        
+        msg!("Slot auction opened: {} epoch {}, {} T+0 slots, ${} -> ${} from {} to {}",
+             asset_symbol, epoch, total_slots, start_price / 1000000, floor_price / 1000000, start_timestamp, end_timestamp);
+        Ok(())
+    }
+
+    /// Buy the next T+0 slot from a running auction at the current Dutch price.
+    /// USDC goes to the protocol fee vault and the slot NFT is minted to the buyer.
+    pub fn buy_auction_slot(
+        ctx: Context<BuyAuctionSlot>,
+        max_price: u64,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        let auction = &mut ctx.accounts.auction;
+        require!(
+            !auction.is_finalized
+                && clock.unix_timestamp >= auction.start_timestamp
+                && clock.unix_timestamp < auction.end_timestamp,
+            ErrorCode::AuctionNotActive
+        );
+        require!(auction.sold < auction.total_slots, ErrorCode::AuctionSoldOut);
+        
+        let price = auction_price(auction, clock.unix_timestamp);
+        require!(price <= max_price, ErrorCode::SlippageExceeded);
+        auction.sold += 1;
+        auction.revenue += price;
+        
+        if price > 0 {
+            anchor_spl::token::transfer(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.buyer_usdc_account.to_account_info(),
+                        to: ctx.accounts.protocol_fee_vault.to_account_info(),
+                        authority: ctx.accounts.buyer.to_account_info(),
+                    },
+                ),
+                price,
+            )?;
+        }
+        
+        let slot_counter = &mut ctx.accounts.slot_counter;
+        let slot_id = slot_counter.next_slot_id;
+        slot_counter.next_slot_id += 1;
+        slot_counter.bump = ctx.bumps.slot_counter;
+        
+        // The sold lot leaves the reservation and joins the curve's outstanding supply
+        let slot_pricing = &mut ctx.accounts.slot_pricing;
+        slot_pricing.reserved = slot_pricing.reserved.saturating_sub(1);
+        slot_pricing.outstanding += 1;
+        slot_pricing.total_minted += 1;
+        slot_pricing.total_revenue += price;
+        
+        let expiry_timestamp = settlement_deadline(
+            load_schedule(&ctx.accounts.trading_schedule)?.as_ref(),
+            clock.unix_timestamp,
+            ctx.accounts.auction.slot_duration,
+        )?;
+        issue_settlement_slot(
+            &mut ctx.accounts.settlement_slot,
+            SlotTerms {
+                slot_id,
+                asset_symbol: ctx.accounts.auction.asset_symbol.clone(),
+                asset_type: ctx.accounts.auction.asset_type,
+                settlement_time: 0,
+                slot_duration: ctx.accounts.auction.slot_duration,
+                mint_price: price,
+                expiry_timestamp,
+                notional_budget: ctx.accounts.auction.notional_budget,
+                creator: ctx.accounts.buyer.key(),
+                bump: ctx.bumps.settlement_slot,
+            },
+            &ctx.accounts.slot_mint.to_account_info(),
+            &ctx.accounts.buyer_token_account.to_account_info(),
+            &ctx.accounts.buyer.to_account_info(),
+            &ctx.accounts.token_2022_program.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        
+        msg!("Auction slot sold: {} epoch {}, ID: {}, Buyer: {:?}, Price: ${}, Sold: {}/{}",
+             ctx.accounts.auction.asset_symbol, ctx.accounts.auction.epoch, slot_id, ctx.accounts.buyer.key(),
+             price / 1000000, ctx.accounts.auction.sold, ctx.accounts.auction.total_slots);
+        Ok(())
+    }
+
+    /// Close out an auction once it has ended or sold out (permissionless). Unsold slots are
+    /// burned: their reservation is released from the supply cap and never minted.
+    pub fn finalize_slot_auction(ctx: Context<FinalizeSlotAuction>) -> Result<()> {
+        let auction = &mut ctx.accounts.auction;
+        require!(!auction.is_finalized, ErrorCode::AuctionNotActive);
+        require!(
+            Clock::get()?.unix_timestamp >= auction.end_timestamp || auction.sold == auction.total_slots,
+            ErrorCode::AuctionNotEnded
+        );
         
-        // Transfer ownership
-        settlement_slot.owner = ctx.accounts.buyer.key();
-        settlement_slot.mint_price = new_price;
+        let unsold = auction.total_slots - auction.sold;
+        let slot_pricing = &mut ctx.accounts.slot_pricing;
+        slot_pricing.reserved = slot_pricing.reserved.saturating_sub(unsold);
+        auction.is_finalized = true;
         
-        msg!("Settlement Slot traded: New owner: {:?}, New price: ${}", 
-             ctx.accounts.buyer.key(), new_price / 1000000);
+        msg!("Slot auction finalized: {} epoch {}, Sold: {}, Burned: {}, Revenue: ${}",
+             auction.asset_symbol, auction.epoch, auction.sold, unsold, auction.revenue / 1000000);
         Ok(())
     }
 
@@ -622,18 +1882,19 @@ pub mod caden {
         bet_seed: u64,
     ) -> Result<()> {
         let bet = &mut ctx.accounts.bet;
-        let multi_oracle = &ctx.accounts.multi_oracle;
         let clock = Clock::get()?;
         
         // Validate bet amount (minimum $10)
         require!(bet_amount >= 10_000000, ErrorCode::BetAmountTooSmall);
+        require_market_open(asset_type, load_schedule(&ctx.accounts.trading_schedule)?.as_ref(), clock.unix_timestamp)?;
         
-        // Get current price from multi-asset oracle
-        let current_price = multi_oracle.asset_prices
-            .iter()
-            .find(|p| p.asset_symbol == asset_symbol && p.asset_type == asset_type)
-            .ok_or(ErrorCode::AssetNotFound)?
-            .price;
+        // Get current price from the configured oracle source
+        let current_price = load_price(
+            &ctx.accounts.oracle_config,
+            &ctx.accounts.price_account,
+            &asset_symbol,
+            asset_type,
+        )?.price;
         
         // Initialize bet account
         bet.owner = ctx.accounts.user.key();
@@ -660,28 +1921,73 @@ pub mod caden {
         bet_id: u64,
         settlement_slot_id: u64,
     ) -> Result<()> {
-        let settlement_slot = &ctx.accounts.settlement_slot;
+        let settlement_slot = &mut ctx.accounts.settlement_slot;
         let bet = &mut ctx.accounts.bet;
-        let multi_oracle = &ctx.accounts.multi_oracle;
         let clock = Clock::get()?;
         
         // Validate settlement slot (must be T+0)
         require!(settlement_slot.slot_id == settlement_slot_id, ErrorCode::InvalidSettlementSlot);
-        require_keys_eq!(settlement_slot.owner, ctx.accounts.user.key(), ErrorCode::Unauthorized);
         require!(settlement_slot.is_active, ErrorCode::SlotExpired);
+        require!(clock.unix_timestamp < settlement_slot.expiry_timestamp, ErrorCode::SlotExpired);
         require!(settlement_slot.settlement_time == 0, ErrorCode::NotInstantSettlement);
         
         // Validate bet
         require!(bet.bet_id == bet_id, ErrorCode::InvalidBetId);
         require_keys_eq!(bet.owner, ctx.accounts.user.key(), ErrorCode::Unauthorized);
         require!(!bet.is_settled, ErrorCode::BetAlreadySettled);
+        require!(
+            settlement_slot.asset_symbol == bet.asset_symbol && settlement_slot.asset_type == bet.asset_type,
+            ErrorCode::SlotAssetMismatch
+        );
         
-        // Get current price from multi-asset oracle
-        let current_price = multi_oracle.asset_prices
-            .iter()
-            .find(|p| p.asset_symbol == bet.asset_symbol && p.asset_type == bet.asset_type)
-            .ok_or(ErrorCode::AssetNotFound)?
-            .price;
+        // The token holder may use the slot. A slot out for rent sits in the rental's escrow:
+        // the renter may use it until the rental expires, and the lender again afterwards.
+        // A fractionalized slot sits in its vault and is used by burning enough fractions
+        // to cover the bet's notional.
+        let user = ctx.accounts.user.key();
+        let holder = ctx.accounts.slot_token_account.owner;
+        let authorized = if let Some(rental) = ctx.accounts.slot_rental.as_ref().filter(|r| r.key() == holder) {
+            if clock.unix_timestamp < rental.rented_until {
+                rental.renter == user
+            } else {
+                rental.lender == user
+            }
+        } else if let Some(vault) = ctx.accounts.fraction_vault.as_mut().filter(|v| v.key() == holder) {
+            let fraction_mint = ctx.accounts.fraction_mint.as_ref().ok_or(ErrorCode::FractionAccountsRequired)?;
+            let user_fraction_account = ctx.accounts.user_fraction_account.as_ref().ok_or(ErrorCode::FractionAccountsRequired)?;
+            let token_program = ctx.accounts.token_program.as_ref().ok_or(ErrorCode::FractionAccountsRequired)?;
+            require_keys_eq!(fraction_mint.key(), vault.fraction_mint, ErrorCode::FractionAccountsRequired);
+            
+            let fractions = bet.bet_amount.div_ceil(vault.notional_per_fraction);
+            anchor_spl::token::burn(
+                CpiContext::new(
+                    token_program.to_account_info(),
+                    Burn {
+                        mint: fraction_mint.to_account_info(),
+                        from: user_fraction_account.to_account_info(),
+                        authority: ctx.accounts.user.to_account_info(),
+                    },
+                ),
+                fractions,
+            )?;
+            vault.fractions_burned += fractions;
+            true
+        } else {
+            holder == user
+        };
+        require!(authorized, ErrorCode::Unauthorized);
+        
+        // Consume the slot's notional budget; it deactivates once exhausted
+        consume_slot_notional(settlement_slot, bet.bet_amount)?;
+        require_market_open(bet.asset_type, load_schedule(&ctx.accounts.trading_schedule)?.as_ref(), clock.unix_timestamp)?;
+        
+        // Get current price from the configured oracle source
+        let current_price = load_price(
+            &ctx.accounts.oracle_config,
+            &ctx.accounts.price_account,
+            &bet.asset_symbol,
+            bet.asset_type,
+        )?.price;
         
         // Calculate P&L based on price movement
         let price_change = if current_price > bet.entry_price {
@@ -726,15 +2032,33 @@ pub mod caden {
 
     /// Initialize Multi-Asset Oracle for cross-asset settlement slots
     pub fn init_multi_asset_oracle(ctx: Context<InitMultiAssetOracle>) -> Result<()> {
-        let multi_oracle = &mut ctx.accounts.multi_oracle;
+        let mut multi_oracle = ctx.accounts.multi_oracle.load_init()?;
         let clock = Clock::get()?;
         
         multi_oracle.admin = ctx.accounts.admin.key();
-        multi_oracle.asset_prices = Vec::new();
         multi_oracle.updated_slot = clock.slot;
+        multi_oracle.len = 0;
+        multi_oracle.max_move_bps_per_update = DEFAULT_MAX_MOVE_BPS_PER_UPDATE;
+        multi_oracle.max_move_bps_per_slot = 0;
+        multi_oracle.override_delay_secs = DEFAULT_OVERRIDE_DELAY_SECS;
         multi_oracle.bump = ctx.bumps.multi_oracle;
         
-        msg!("Multi-Asset Oracle initialized by admin: {:?}", ctx.accounts.admin.key());
+        msg!("Multi-Asset Oracle initialized by admin: {:?}, capacity: {}", ctx.accounts.admin.key(), DEFAULT_ORACLE_CAPACITY);
+        Ok(())
+    }
+
+    /// Grow the multi-asset oracle to hold more assets (admin only)
+    pub fn grow_multi_asset_oracle(ctx: Context<GrowMultiAssetOracle>, new_capacity: u32) -> Result<()> {
+        require_keys_eq!(ctx.accounts.multi_oracle.load()?.admin, ctx.accounts.admin.key(), ErrorCode::Unauthorized);
+        
+        grow_table::<MultiAssetOracle, AssetPrice>(
+            &ctx.accounts.multi_oracle.to_account_info(),
+            &ctx.accounts.admin.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            new_capacity as usize,
+        )?;
+        
+        msg!("Multi-Asset Oracle capacity grown to {}", new_capacity);
         Ok(())
     }
 
@@ -745,35 +2069,56 @@ pub mod caden {
         asset_type: AssetType,
         price: u64,
     ) -> Result<()> {
-        let multi_oracle = &mut ctx.accounts.multi_oracle;
         let clock = Clock::get()?;
         
-        // Validate admin
-        require_keys_eq!(multi_oracle.admin, ctx.accounts.admin.key(), ErrorCode::Unauthorized);
-        
-        // Find existing asset or add new one
-        let mut found = false;
-        for asset_price in multi_oracle.asset_prices.iter_mut() {
-            if asset_price.asset_symbol == asset_symbol && asset_price.asset_type == asset_type {
-                asset_price.price = price;
-                asset_price.last_updated = clock.slot;
-                found = true;
-                break;
-            }
-        }
+        require!(asset_symbol.len() <= 10, ErrorCode::InvalidAssetSymbol);
         
-        if !found {
-            multi_oracle.asset_prices.push(AssetPrice {
-                asset_symbol: asset_symbol.clone(),
-                asset_type,
+        {
+            let multi_oracle_info = ctx.accounts.multi_oracle.to_account_info();
+            
+            // Validate admin or scoped updater
+            authorize_updater(
+                &multi_oracle_info,
+                &ctx.accounts.updater.key(),
+                ctx.accounts.updater_role.as_deref(),
+                Some((&asset_symbol, asset_type)),
+            )?;
+            
+            let (mut multi_oracle, mut asset_prices) =
+                load_table_mut::<MultiAssetOracle, AssetPrice>(&multi_oracle_info)?;
+            
+            // Find existing asset or add new one
+            let index = find_or_insert(
+                &mut asset_prices,
+                &mut multi_oracle.len,
+                encode_symbol(&asset_symbol),
+                asset_type as u8,
+            )?;
+            let asset_price = &mut asset_prices[index];
+            check_price_move(
+                MoveLimits {
+                    max_move_bps_per_update: multi_oracle.max_move_bps_per_update,
+                    max_move_bps_per_slot: multi_oracle.max_move_bps_per_slot,
+                    override_delay_secs: multi_oracle.override_delay_secs,
+                },
+                asset_price.price,
+                asset_price.last_updated,
                 price,
-                last_updated: clock.slot,
-                source: PriceSource::Manual,
-                confidence: 0,
-            });
+                clock.slot,
+            )?;
+            asset_price.price = price;
+            asset_price.last_updated = clock.slot;
+            asset_price.source = PriceSource::Manual as u8;
+            asset_price.updated_by = ctx.accounts.updater.key();
+            
+            multi_oracle.updated_slot = clock.slot;
         }
         
-        multi_oracle.updated_slot = clock.slot;
+        let accumulator = &mut ctx.accounts.price_accumulator;
+        init_accumulator(accumulator, &asset_symbol, asset_type, ctx.bumps.price_accumulator);
+        record_active_price(accumulator, &ctx.accounts.oracle_config, &[(OracleMode::MultiAsset, price)], clock.slot);
+        
+        record_history(&mut *ctx.accounts.price_history.load_mut()?, clock.slot, price, 0, PriceSource::Manual);
         
         msg!("Asset price updated: {} ({:?}) = ${}", asset_symbol, asset_type, price / 1000000);
         Ok(())
@@ -781,203 +2126,432 @@ pub mod caden {
 
     /// Initialize Oracle Aggregator with multiple price sources
     pub fn init_oracle_aggregator(ctx: Context<InitOracleAggregator>) -> Result<()> {
-        let aggregator = &mut ctx.accounts.oracle_aggregator;
+        let mut aggregator = ctx.accounts.oracle_aggregator.load_init()?;
         let clock = Clock::get()?;
         
         aggregator.admin = ctx.accounts.admin.key();
-        aggregator.enabled_sources = vec![
+        aggregator.enabled_sources = source_mask(&[
             PriceSource::Pyth,
             PriceSource::Switchboard,
             PriceSource::CoinGecko,
-        ];
-        aggregator.price_feeds = Vec::new();
+        ]);
+        aggregator.len = 0;
         aggregator.update_frequency = 400; // Update every 400 slots (~3 minutes)
         aggregator.last_crank_slot = clock.slot;
         aggregator.deviation_threshold = 500; // 5% deviation threshold
+        aggregator.max_move_bps_per_update = DEFAULT_MAX_MOVE_BPS_PER_UPDATE;
+        aggregator.max_move_bps_per_slot = 0;
+        aggregator.override_delay_secs = DEFAULT_OVERRIDE_DELAY_SECS;
         aggregator.bump = ctx.bumps.oracle_aggregator;
         
-        msg!("Oracle Aggregator initialized with multiple price sources");
+        msg!("Oracle Aggregator initialized with multiple price sources, capacity: {}", DEFAULT_ORACLE_CAPACITY);
+        Ok(())
+    }
+
+    /// Grow the oracle aggregator to hold more price feeds (admin only)
+    pub fn grow_oracle_aggregator(ctx: Context<GrowOracleAggregator>, new_capacity: u32) -> Result<()> {
+        require_keys_eq!(ctx.accounts.oracle_aggregator.load()?.admin, ctx.accounts.admin.key(), ErrorCode::Unauthorized);
+        
+        grow_table::<OracleAggregator, PriceFeed>(
+            &ctx.accounts.oracle_aggregator.to_account_info(),
+            &ctx.accounts.admin.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            new_capacity as usize,
+        )?;
+        
+        msg!("Oracle Aggregator capacity grown to {}", new_capacity);
+        Ok(())
+    }
+
+    /// Pin the Pyth and Switchboard accounts an asset's feed is pulled from (aggregator admin
+    /// or governance). The default pubkey disables that source for the asset.
+    pub fn set_price_feed_accounts(
+        ctx: Context<SetPriceFeedAccounts>,
+        asset_symbol: String,
+        asset_type: AssetType,
+        pyth_account: Pubkey,
+        switchboard_account: Pubkey,
+    ) -> Result<()> {
+        require!(asset_symbol.len() <= 10, ErrorCode::InvalidAssetSymbol);
+        
+        let aggregator_info = ctx.accounts.oracle_aggregator.to_account_info();
+        authorize_oracle_authority(&aggregator_info, &ctx.accounts.authority.key(), ctx.accounts.governance.as_deref())?;
+        
+        let (mut aggregator, mut price_feeds) = load_table_mut::<OracleAggregator, PriceFeed>(&aggregator_info)?;
+        let index = find_or_insert(
+            &mut price_feeds,
+            &mut aggregator.len,
+            encode_symbol(&asset_symbol),
+            asset_type as u8,
+        )?;
+        let feed = &mut price_feeds[index];
+        
+        // A price pulled from a replaced account must not keep feeding the median
+        if feed.pyth_account != pyth_account {
+            feed.pyth_price = 0;
+        }
+        if feed.switchboard_account != switchboard_account {
+            feed.switchboard_price = 0;
+        }
+        feed.pyth_account = pyth_account;
+        feed.switchboard_account = switchboard_account;
+        feed.aggregated_price = calculate_aggregated_price(
+            feed.pyth_price,
+            feed.switchboard_price,
+            feed.external_price,
+        );
+        
+        msg!("Price feed accounts for {} ({:?}): Pyth {:?}, Switchboard {:?}",
+             asset_symbol, asset_type, pyth_account, switchboard_account);
         Ok(())
     }
 
-    /// Update price from REAL Pyth oracle
+    /// Pull an asset's price from its pinned Pyth account (aggregator admin or scoped updater)
     pub fn update_price_from_pyth(
         ctx: Context<UpdatePriceFromPyth>,
         asset_symbol: String,
         asset_type: AssetType,
     ) -> Result<()> {
-        let aggregator = &mut ctx.accounts.oracle_aggregator;
         let clock = Clock::get()?;
         
-        // Load REAL Pyth price feed
-        let price_account_info = &ctx.accounts.pyth_price_account;
-        let price_feed = load_price_feed_from_account_info(price_account_info)
-            .map_err(|_| ErrorCode::InvalidPriceData)?;
-        
-        // Get current price from Pyth (unchecked for demo - in production use get_price_no_older_than)
-        let pyth_price_data = price_feed.get_price_unchecked();
-        
-        // Convert Pyth price to our format (6 decimals)
-        // Pyth prices have expo (usually -8), we need to normalize to 6 decimals
-        let price_i64 = pyth_price_data.price;
-        let expo = pyth_price_data.expo;
-        
-        // Convert to 6 decimal places
-        let pyth_price = if expo >= -6 {
-            // If expo is -6 or greater, scale up
-            (price_i64 as u64) * 10_u64.pow((expo + 6) as u32)
-        } else {
-            // If expo is less than -6, scale down
-            (price_i64 as u64) / 10_u64.pow((-expo - 6) as u32)
-        };
+        require!(asset_symbol.len() <= 10, ErrorCode::InvalidAssetSymbol);
         
-        // Get confidence interval
-        let confidence = (pyth_price_data.conf as u64) / 10_u64.pow((-expo - 6) as u32);
+        let reading = read_pyth_price(&ctx.accounts.pyth_price_account, &clock)?;
+        let pyth_price = reading.price;
+        let confidence = reading.confidence;
         
         msg!("📊 REAL Pyth price loaded: {} = ${} (conf: ${})", 
              asset_symbol, pyth_price / 1_000_000, confidence / 1_000_000);
         
-        // Find or create price feed
-        let mut found = false;
-        for feed in aggregator.price_feeds.iter_mut() {
-            if feed.asset_symbol == asset_symbol && feed.asset_type == asset_type {
-                feed.pyth_price = pyth_price;
-                feed.last_updated = clock.slot;
-                feed.is_stale = false;
-                
-                // Recalculate aggregated price
-                feed.aggregated_price = calculate_aggregated_price(
-                    feed.pyth_price,
-                    feed.switchboard_price,
-                    feed.external_price,
-                );
-                found = true;
-                break;
-            }
-        }
+        let aggregated_price = {
+            let aggregator_info = ctx.accounts.oracle_aggregator.to_account_info();
+            
+            // Validate admin or scoped updater
+            authorize_updater(
+                &aggregator_info,
+                &ctx.accounts.updater.key(),
+                ctx.accounts.updater_role.as_deref(),
+                Some((&asset_symbol, asset_type)),
+            )?;
+            
+            let limits = move_limits(&aggregator_info)?;
+            let (_, mut price_feeds) = load_table_mut::<OracleAggregator, PriceFeed>(&aggregator_info)?;
+            let index = find_entry(&price_feeds, &encode_symbol(&asset_symbol), asset_type as u8)
+                .ok_or(ErrorCode::AssetNotFound)?;
+            let feed = &mut price_feeds[index];
+            require!(
+                feed.pyth_account != Pubkey::default() && feed.pyth_account == ctx.accounts.pyth_price_account.key(),
+                ErrorCode::PriceFeedAccountMismatch
+            );
+            
+            // Write the Pyth column and recalculate the aggregated price, both within move limits
+            write_feed_price(feed, PriceSource::Pyth, limits, pyth_price, clock.slot, ctx.accounts.updater.key())?
+        };
         
-        if !found {
-            aggregator.price_feeds.push(PriceFeed {
-                asset_symbol: asset_symbol.clone(),
-                asset_type,
-                pyth_price,
-                switchboard_price: 0,
-                external_price: 0,
-                aggregated_price: pyth_price,
-                last_updated: clock.slot,
-                is_stale: false,
-            });
-        }
+        let accumulator = &mut ctx.accounts.price_accumulator;
+        init_accumulator(accumulator, &asset_symbol, asset_type, ctx.bumps.price_accumulator);
+        record_active_price(
+            accumulator,
+            &ctx.accounts.oracle_config,
+            &[(OracleMode::Pyth, pyth_price), (OracleMode::Aggregated, aggregated_price)],
+            clock.slot,
+        );
+        
+        record_history(&mut *ctx.accounts.price_history.load_mut()?, clock.slot, pyth_price, confidence, PriceSource::Pyth);
         
         msg!("✅ REAL Pyth price updated: {} ({:?}) = ${}", asset_symbol, asset_type, pyth_price / 1000000);
         Ok(())
     }
 
-    /// Update price from Switchboard oracle
+    /// Pull an asset's price from its pinned Switchboard aggregator (aggregator admin or scoped updater)
     pub fn update_price_from_switchboard(
         ctx: Context<UpdatePriceFromSwitchboard>,
         asset_symbol: String,
         asset_type: AssetType,
     ) -> Result<()> {
-        let aggregator = &mut ctx.accounts.oracle_aggregator;
         let clock = Clock::get()?;
         
-        // In production, read from Switchboard aggregator
-        let switchboard_price = 45100_000000; // Simulated Switchboard price
-        
-        // Find or create price feed
-        let mut found = false;
-        for feed in aggregator.price_feeds.iter_mut() {
-            if feed.asset_symbol == asset_symbol && feed.asset_type == asset_type {
-                feed.switchboard_price = switchboard_price;
-                feed.last_updated = clock.slot;
-                feed.is_stale = false;
-                
-                // Recalculate aggregated price
-                feed.aggregated_price = calculate_aggregated_price(
-                    feed.pyth_price,
-                    feed.switchboard_price,
-                    feed.external_price,
-                );
-                found = true;
-                break;
-            }
-        }
+        require!(asset_symbol.len() <= 10, ErrorCode::InvalidAssetSymbol);
         
-        if !found {
-            aggregator.price_feeds.push(PriceFeed {
-                asset_symbol: asset_symbol.clone(),
-                asset_type,
-                pyth_price: 0,
+        let switchboard_price = read_switchboard_price(&ctx.accounts.switchboard_aggregator, &clock)?.price;
+        
+        let aggregated_price = {
+            let aggregator_info = ctx.accounts.oracle_aggregator.to_account_info();
+            
+            // Validate admin or scoped updater
+            authorize_updater(
+                &aggregator_info,
+                &ctx.accounts.updater.key(),
+                ctx.accounts.updater_role.as_deref(),
+                Some((&asset_symbol, asset_type)),
+            )?;
+            
+            let limits = move_limits(&aggregator_info)?;
+            let (_, mut price_feeds) = load_table_mut::<OracleAggregator, PriceFeed>(&aggregator_info)?;
+            let index = find_entry(&price_feeds, &encode_symbol(&asset_symbol), asset_type as u8)
+                .ok_or(ErrorCode::AssetNotFound)?;
+            let feed = &mut price_feeds[index];
+            require!(
+                feed.switchboard_account != Pubkey::default()
+                    && feed.switchboard_account == ctx.accounts.switchboard_aggregator.key(),
+                ErrorCode::PriceFeedAccountMismatch
+            );
+            
+            // Write the Switchboard column and recalculate the aggregated price, both within move limits
+            write_feed_price(
+                feed,
+                PriceSource::Switchboard,
+                limits,
                 switchboard_price,
-                external_price: 0,
-                aggregated_price: switchboard_price,
-                last_updated: clock.slot,
-                is_stale: false,
-            });
-        }
+                clock.slot,
+                ctx.accounts.updater.key(),
+            )?
+        };
+        
+        let accumulator = &mut ctx.accounts.price_accumulator;
+        init_accumulator(accumulator, &asset_symbol, asset_type, ctx.bumps.price_accumulator);
+        record_active_price(
+            accumulator,
+            &ctx.accounts.oracle_config,
+            &[(OracleMode::Switchboard, switchboard_price), (OracleMode::Aggregated, aggregated_price)],
+            clock.slot,
+        );
+        
+        record_history(&mut *ctx.accounts.price_history.load_mut()?, clock.slot, switchboard_price, 0, PriceSource::Switchboard);
         
         msg!("Price updated from Switchboard: {} ({:?}) = ${}", asset_symbol, asset_type, switchboard_price / 1000000);
         Ok(())
     }
 
-    /// Update price from external API (CoinGecko, Twelve Data, etc.)
-    pub fn update_price_from_external(
-        ctx: Context<UpdatePriceFromExternal>,
-        asset_symbol: String,
-        asset_type: AssetType,
-        price: u64,
+    /// Register or rescope an off-chain price publisher key (aggregator admin or governance).
+    /// The publisher may only attest prices for `asset_type_mask` and `asset_symbols` (empty = any symbol).
+    pub fn register_price_publisher(
+        ctx: Context<RegisterPricePublisher>,
+        publisher: Pubkey,
         source: PriceSource,
+        max_staleness_secs: i64,
+        asset_type_mask: u8,
+        asset_symbols: Vec<String>,
     ) -> Result<()> {
-        let aggregator = &mut ctx.accounts.oracle_aggregator;
-        let clock = Clock::get()?;
-        
-        // Validate admin
-        require_keys_eq!(aggregator.admin, ctx.accounts.admin.key(), ErrorCode::Unauthorized);
-        
-        // Validate price source
+        authorize_oracle_authority(
+            &ctx.accounts.oracle_aggregator.to_account_info(),
+            &ctx.accounts.authority.key(),
+            ctx.accounts.governance.as_deref(),
+        )?;
         require!(
             matches!(source, PriceSource::CoinGecko | PriceSource::TwelveData | PriceSource::Binance),
             ErrorCode::InvalidPriceSource
         );
+        require!(max_staleness_secs > 0, ErrorCode::InvalidAttestationTimestamp);
+        require!(asset_type_mask != 0, ErrorCode::UpdaterOutOfScope);
+        require!(asset_symbols.len() <= MAX_UPDATER_ASSETS, ErrorCode::TooManyUpdaterAssets);
+        require!(asset_symbols.iter().all(|s| s.len() <= 10), ErrorCode::InvalidAssetSymbol);
+        
+        // Re-registering keeps the nonce/timestamp watermark so old attestations stay dead
+        let price_publisher = &mut ctx.accounts.price_publisher;
+        price_publisher.publisher = publisher;
+        price_publisher.source = source;
+        price_publisher.max_staleness_secs = max_staleness_secs;
+        price_publisher.asset_type_mask = asset_type_mask;
+        price_publisher.asset_symbols = asset_symbols;
+        price_publisher.is_active = true;
+        price_publisher.added_by = ctx.accounts.authority.key();
+        price_publisher.added_slot = Clock::get()?.slot;
+        price_publisher.bump = ctx.bumps.price_publisher;
+        
+        msg!("Price publisher {:?} registered for {:?} (max staleness {}s, asset types: {:#07b}, symbols: {:?})",
+             publisher, source, max_staleness_secs, asset_type_mask, price_publisher.asset_symbols);
+        Ok(())
+    }
+
+    /// Stop accepting attestations from a price publisher (aggregator admin or governance)
+    pub fn deactivate_price_publisher(ctx: Context<DeactivatePricePublisher>) -> Result<()> {
+        authorize_oracle_authority(
+            &ctx.accounts.oracle_aggregator.to_account_info(),
+            &ctx.accounts.authority.key(),
+            ctx.accounts.governance.as_deref(),
+        )?;
+        
+        let price_publisher = &mut ctx.accounts.price_publisher;
+        price_publisher.is_active = false;
+        
+        msg!("Price publisher {:?} deactivated", price_publisher.publisher);
+        Ok(())
+    }
+
+    /// Submit a publisher-signed price attestation. Anyone may relay it; the transaction
+    /// must carry an Ed25519 program instruction verifying the publisher's signature
+    /// immediately before this one.
+    pub fn submit_price_attestation(
+        ctx: Context<SubmitPriceAttestation>,
+        attestation: PriceAttestation,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
         
-        // Find or create price feed
-        let mut found = false;
-        for feed in aggregator.price_feeds.iter_mut() {
-            if feed.asset_symbol == asset_symbol && feed.asset_type == asset_type {
-                feed.external_price = price;
-                feed.last_updated = clock.slot;
-                feed.is_stale = false;
-                
-                // Recalculate aggregated price
-                feed.aggregated_price = calculate_aggregated_price(
-                    feed.pyth_price,
-                    feed.switchboard_price,
-                    feed.external_price,
-                );
-                found = true;
-                break;
-            }
-        }
+        require!(attestation.asset_symbol.len() <= 10, ErrorCode::InvalidAssetSymbol);
+        require!(attestation.price > 0, ErrorCode::InvalidPriceData);
         
-        if !found {
-            aggregator.price_feeds.push(PriceFeed {
-                asset_symbol: asset_symbol.clone(),
-                asset_type,
-                pyth_price: 0,
-                switchboard_price: 0,
-                external_price: price,
-                aggregated_price: price,
-                last_updated: clock.slot,
-                is_stale: false,
-            });
-        }
+        let publisher_key = ctx.accounts.price_publisher.publisher;
+        let message = attestation_message(&attestation)?;
+        verify_ed25519_signature(&ctx.accounts.instructions_sysvar, &publisher_key, &message)?;
+        check_and_consume_attestation(&mut ctx.accounts.price_publisher, &attestation, clock.unix_timestamp)?;
+        
+        let aggregated_price = {
+            let aggregator_info = ctx.accounts.oracle_aggregator.to_account_info();
+            let (mut aggregator, mut price_feeds) =
+                load_table_mut::<OracleAggregator, PriceFeed>(&aggregator_info)?;
+            
+            let index = find_or_insert(
+                &mut price_feeds,
+                &mut aggregator.len,
+                encode_symbol(&attestation.asset_symbol),
+                attestation.asset_type as u8,
+            )?;
+            let limits = MoveLimits {
+                max_move_bps_per_update: aggregator.max_move_bps_per_update,
+                max_move_bps_per_slot: aggregator.max_move_bps_per_slot,
+                override_delay_secs: aggregator.override_delay_secs,
+            };
+            let feed = &mut price_feeds[index];
+            write_feed_price(feed, attestation.source, limits, attestation.price, clock.slot, publisher_key)?
+        };
+        
+        let accumulator = &mut ctx.accounts.price_accumulator;
+        init_accumulator(accumulator, &attestation.asset_symbol, attestation.asset_type, ctx.bumps.price_accumulator);
+        record_active_price(accumulator, &ctx.accounts.oracle_config, &[(OracleMode::Aggregated, aggregated_price)], clock.slot);
+        
+        record_history(
+            &mut *ctx.accounts.price_history.load_mut()?,
+            clock.slot,
+            attestation.price,
+            attestation.confidence,
+            attestation.source,
+        );
         
-        msg!("Price updated from {:?}: {} ({:?}) = ${}", source, asset_symbol, asset_type, price / 1000000);
+        msg!("Attested price from {:?} via {:?}: {} ({:?}) = ${} (nonce {})",
+             attestation.source, publisher_key, attestation.asset_symbol, attestation.asset_type,
+             attestation.price / 1000000, attestation.nonce);
         Ok(())
     }
 
-    /// Create a new settlement slot AMM pool
+    /// Create the tenor vault for one asset and T+N (permissionless). The vault holds wrapped
+    /// settlement slots and is the mint authority of their fungible tenor token.
+    pub fn create_tenor_vault(
+        ctx: Context<CreateTenorVault>,
+        asset_symbol: String,
+        asset_type: AssetType,
+        settlement_time: u64,
+    ) -> Result<()> {
+        require!(asset_symbol.len() <= 10, ErrorCode::InvalidAssetSymbol);
+        require!(settlement_time <= 365, ErrorCode::InvalidSettlementTime);
+        
+        let vault = &mut ctx.accounts.tenor_vault;
+        vault.asset_symbol = asset_symbol;
+        vault.asset_type = asset_type;
+        vault.settlement_time = settlement_time;
+        vault.tenor_mint = ctx.accounts.tenor_mint.key();
+        vault.wrapped = 0;
+        vault.bump = ctx.bumps.tenor_vault;
+        
+        msg!("Tenor vault created: {} T+{}, Mint: {:?}",
+             vault.asset_symbol, settlement_time, vault.tenor_mint);
+        Ok(())
+    }
+    
+    /// Wrap an unused, unexpired settlement slot into one tenor token of its asset and T+N.
+    /// The slot token moves into the tenor vault, so tenor token supply always equals the
+    /// number of wrapped slots. A wrapped slot cannot settle, be listed or be swept; once it
+    /// expires it must be unwrapped before expire_settlement_slot can close it.
+    pub fn wrap_settlement_slot(ctx: Context<WrapSettlementSlot>) -> Result<()> {
+        let settlement_slot = &ctx.accounts.settlement_slot;
+        require!(settlement_slot.is_tradable, ErrorCode::SlotNotTradable);
+        require!(settlement_slot.is_active, ErrorCode::SlotExpired);
+        require!(Clock::get()?.unix_timestamp < settlement_slot.expiry_timestamp, ErrorCode::SlotExpired);
+        // Only untouched slots are interchangeable; a partly used T+0 budget is not
+        require!(settlement_slot.notional_used == 0, ErrorCode::SlotPartiallyUsed);
+        
+        transfer_slot_token(
+            &ctx.accounts.token_2022_program.to_account_info(),
+            &ctx.accounts.slot_mint.to_account_info(),
+            &ctx.accounts.owner_token_account.to_account_info(),
+            &ctx.accounts.escrow_token_account.to_account_info(),
+            &ctx.accounts.owner.to_account_info(),
+            &[],
+        )?;
+        
+        let vault = &mut ctx.accounts.tenor_vault;
+        vault.wrapped += 1;
+        
+        let time_bytes = vault.settlement_time.to_le_bytes();
+        let seeds = &[
+            b"tenor_vault",
+            vault.asset_symbol.as_bytes(),
+            &[vault.asset_type as u8],
+            &time_bytes,
+            &[vault.bump],
+        ];
+        let signer = &[&seeds[..]];
+        anchor_spl::token::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.tenor_mint.to_account_info(),
+                    to: ctx.accounts.owner_tenor_account.to_account_info(),
+                    authority: vault.to_account_info(),
+                },
+                signer,
+            ),
+            1,
+        )?;
+        
+        msg!("Settlement Slot wrapped: Slot: {:?}, {} T+{}, Wrapped: {}",
+             settlement_slot.key(), vault.asset_symbol, vault.settlement_time, vault.wrapped);
+        Ok(())
+    }
+    
+    /// Burn one tenor token and take the chosen wrapped slot of that asset and T+N out of the
+    /// tenor vault. Expired wrapped slots can still be unwrapped, then swept.
+    pub fn unwrap_settlement_slot(ctx: Context<UnwrapSettlementSlot>) -> Result<()> {
+        anchor_spl::token::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.tenor_mint.to_account_info(),
+                    from: ctx.accounts.holder_tenor_account.to_account_info(),
+                    authority: ctx.accounts.holder.to_account_info(),
+                },
+            ),
+            1,
+        )?;
+        
+        let vault = &mut ctx.accounts.tenor_vault;
+        vault.wrapped -= 1;
+        
+        let time_bytes = vault.settlement_time.to_le_bytes();
+        release_slot_escrow(
+            &ctx.accounts.token_2022_program,
+            &ctx.accounts.slot_mint,
+            &ctx.accounts.escrow_token_account,
+            &ctx.accounts.holder_token_account,
+            &vault.to_account_info(),
+            &[
+                b"tenor_vault",
+                vault.asset_symbol.as_bytes(),
+                &[vault.asset_type as u8],
+                &time_bytes,
+                &[vault.bump],
+            ],
+            &ctx.accounts.holder.to_account_info(),
+        )?;
+        
+        msg!("Settlement Slot unwrapped: Slot: {:?}, Holder: {:?}, Wrapped: {}",
+             ctx.accounts.settlement_slot.key(), ctx.accounts.holder.key(), vault.wrapped);
+        Ok(())
+    }
+    
+    /// Create a new settlement slot AMM pool (governance admin only). A pool quotes one T+N tenor
+    /// by pairing USDC with that tenor's slot-backed tenor token (see wrap_settlement_slot), so
+    /// its price is the market price of one unused slot of the asset and T+N.
     pub fn create_settlement_slot_pool(
         ctx: Context<CreateSettlementSlotPool>,
         asset_symbol: String,
@@ -999,74 +2573,337 @@ pub mod caden {
         pool.asset_type = asset_type;
         pool.settlement_time = settlement_time;
         pool.token_a_mint = ctx.accounts.usdc_mint.key();
-        pool.token_b_mint = ctx.accounts.slot_nft_mint.key();
+        pool.token_b_mint = ctx.accounts.tenor_mint.key();
         pool.token_a_vault = ctx.accounts.token_a_vault.key();
         pool.token_b_vault = ctx.accounts.token_b_vault.key();
         pool.token_a_amount = 0;
         pool.token_b_amount = 0;
         pool.fee_rate = fee_rate;
         pool.pool_authority = ctx.accounts.pool_authority.key();
+        pool.lp_mint = ctx.accounts.lp_mint.key();
+        pool.lp_supply = 0;
+        pool.creator = ctx.accounts.creator.key();
         pool.created_slot = clock.slot;
         pool.is_active = true;
         pool.bump = ctx.bumps.pool;
         
+        // List the canonical pool for this asset/tenor so routers can discover it
+        let registry = &mut ctx.accounts.pool_registry;
+        require!(registry.pools.len() < MAX_REGISTERED_POOLS, ErrorCode::PoolRegistryFull);
+        registry.bump = ctx.bumps.pool_registry;
+        registry.pools.push(RegisteredPool {
+            asset_symbol: asset_symbol.clone(),
+            asset_type,
+            settlement_time,
+            pool: pool.key(),
+        });
+        
         msg!("Settlement Slot Pool created: {} ({:?}) T+{}, Fee: {} bps", 
              asset_symbol, asset_type, settlement_time, fee_rate);
         Ok(())
     }
 
-    /// Add liquidity to settlement slot pool
+    /// Add USDC and tenor tokens to a settlement slot pool, minting LP tokens for the pro-rata share
     pub fn add_liquidity_to_pool(
         ctx: Context<AddLiquidityToPool>,
         usdc_amount: u64,
-        slot_nft_amount: u64,
+        tenor_amount: u64,
+        min_lp_tokens: u64,
     ) -> Result<()> {
-        let pool = &mut ctx.accounts.pool;
+        let pool = &ctx.accounts.pool;
         
         // Validate pool is active
         require!(pool.is_active, ErrorCode::PoolInactive);
+        require!(usdc_amount > 0 && tenor_amount > 0, ErrorCode::InvalidAmount);
+        
+        let (lp_tokens_to_mint, locked_liquidity) = pool_lp_tokens_for_deposit(
+            pool.lp_supply,
+            pool.token_a_amount,
+            pool.token_b_amount,
+            usdc_amount,
+            tenor_amount,
+        )?;
+        require!(lp_tokens_to_mint >= min_lp_tokens, ErrorCode::SlippageExceeded);
+        
+        // Transfer USDC and tenor tokens from user to pool vaults
+        anchor_spl::token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.user_token_a.to_account_info(),
+                    to: ctx.accounts.token_a_vault.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            usdc_amount,
+        )?;
+        anchor_spl::token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.user_token_b.to_account_info(),
+                    to: ctx.accounts.token_b_vault.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            tenor_amount,
+        )?;
+        
+        // Mint LP tokens to user, signed by the pool authority PDA
+        let pool_key = pool.key();
+        let seeds = &[
+            b"pool_authority",
+            pool_key.as_ref(),
+            &[ctx.bumps.pool_authority],
+        ];
+        let signer = &[&seeds[..]];
+        anchor_spl::token::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.lp_mint.to_account_info(),
+                    to: ctx.accounts.user_lp_account.to_account_info(),
+                    authority: ctx.accounts.pool_authority.to_account_info(),
+                },
+                signer,
+            ),
+            lp_tokens_to_mint,
+        )?;
         
         // Update pool amounts
+        let pool = &mut ctx.accounts.pool;
         pool.token_a_amount = pool.token_a_amount.checked_add(usdc_amount)
             .ok_or(ErrorCode::MathOverflow)?;
-        pool.token_b_amount = pool.token_b_amount.checked_add(slot_nft_amount)
+        pool.token_b_amount = pool.token_b_amount.checked_add(tenor_amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        pool.lp_supply = pool.lp_supply.checked_add(lp_tokens_to_mint + locked_liquidity)
+            .ok_or(ErrorCode::MathOverflow)?;
+        
+        msg!("Liquidity added to pool {}: USDC: {}, tenor tokens: {} → {} LP tokens", 
+             pool.pool_id, usdc_amount, tenor_amount, lp_tokens_to_mint);
+        Ok(())
+    }
+
+    /// Burn LP tokens for a pro-rata share of the pool's USDC and tenor tokens
+    pub fn remove_liquidity_from_pool(
+        ctx: Context<RemoveLiquidityFromPool>,
+        lp_amount: u64,
+        min_usdc_out: u64,
+        min_slots_out: u64,
+    ) -> Result<()> {
+        let pool = &ctx.accounts.pool;
+        require!(lp_amount > 0, ErrorCode::InvalidAmount);
+        require!(pool.lp_supply > 0, ErrorCode::InsufficientLiquidity);
+        
+        let usdc_out = (lp_amount as u128 * pool.token_a_amount as u128 / pool.lp_supply as u128) as u64;
+        let slots_out = (lp_amount as u128 * pool.token_b_amount as u128 / pool.lp_supply as u128) as u64;
+        require!(usdc_out > 0 || slots_out > 0, ErrorCode::InsufficientLiquidity);
+        require!(usdc_out >= min_usdc_out && slots_out >= min_slots_out, ErrorCode::SlippageExceeded);
+        
+        // Burn the user's LP tokens
+        anchor_spl::token::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.lp_mint.to_account_info(),
+                    from: ctx.accounts.user_lp_account.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            lp_amount,
+        )?;
+        
+        // Pay out both sides, signed by the pool authority PDA
+        let pool_key = pool.key();
+        let seeds = &[
+            b"pool_authority",
+            pool_key.as_ref(),
+            &[ctx.bumps.pool_authority],
+        ];
+        let signer = &[&seeds[..]];
+        anchor_spl::token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.token_a_vault.to_account_info(),
+                    to: ctx.accounts.user_token_a.to_account_info(),
+                    authority: ctx.accounts.pool_authority.to_account_info(),
+                },
+                signer,
+            ),
+            usdc_out,
+        )?;
+        anchor_spl::token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.token_b_vault.to_account_info(),
+                    to: ctx.accounts.user_token_b.to_account_info(),
+                    authority: ctx.accounts.pool_authority.to_account_info(),
+                },
+                signer,
+            ),
+            slots_out,
+        )?;
+        
+        // Update pool amounts
+        let pool = &mut ctx.accounts.pool;
+        pool.token_a_amount = pool.token_a_amount.checked_sub(usdc_out)
+            .ok_or(ErrorCode::MathOverflow)?;
+        pool.token_b_amount = pool.token_b_amount.checked_sub(slots_out)
+            .ok_or(ErrorCode::MathOverflow)?;
+        pool.lp_supply = pool.lp_supply.checked_sub(lp_amount)
             .ok_or(ErrorCode::MathOverflow)?;
         
-        msg!("Liquidity added to pool {}: USDC: {}, Slots: {}", 
-             pool.pool_id, usdc_amount, slot_nft_amount);
+        msg!("Liquidity removed from pool {}: {} LP tokens → USDC: {}, tenor tokens: {}", 
+             pool.pool_id, lp_amount, usdc_out, slots_out);
         Ok(())
     }
 
-    /// Swap USDC for settlement slot NFTs
+    /// Swap USDC for the pool's tenor tokens (constant product, fee stays with LPs)
     pub fn swap_usdc_for_slots(
         ctx: Context<SwapUsdcForSlots>,
         usdc_amount: u64,
         min_slots_out: u64,
     ) -> Result<()> {
-        let pool = &mut ctx.accounts.pool;
-        
-        // Validate pool is active
+        let pool = &ctx.accounts.pool;
         require!(pool.is_active, ErrorCode::PoolInactive);
+        require!(usdc_amount > 0, ErrorCode::InvalidAmount);
+        
+        let (slots_out, fee_amount) =
+            pool_swap_exact_in(pool.token_a_amount, pool.token_b_amount, usdc_amount, pool.fee_rate)?;
+        require!(slots_out >= min_slots_out, ErrorCode::InsufficientOutputAmount);
+        
+        let accounts = &ctx.accounts;
+        pool_swap_transfers(
+            &accounts.token_program,
+            &accounts.user,
+            &accounts.pool_authority,
+            &pool.key(),
+            ctx.bumps.pool_authority,
+            (&accounts.user_token_a, &accounts.token_a_vault, usdc_amount),
+            (&accounts.token_b_vault, &accounts.user_token_b, slots_out),
+        )?;
         
-        // Calculate swap (simplified constant product formula)
-        let fee_amount = (usdc_amount * pool.fee_rate as u64) / 10000;
-        let usdc_after_fee = usdc_amount.checked_sub(fee_amount)
+        let pool = &mut ctx.accounts.pool;
+        pool.token_a_amount = pool.token_a_amount.checked_add(usdc_amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        pool.token_b_amount = pool.token_b_amount.checked_sub(slots_out)
             .ok_or(ErrorCode::MathOverflow)?;
         
-        let slots_out = (usdc_after_fee * pool.token_b_amount) / pool.token_a_amount;
-        require!(slots_out >= min_slots_out, ErrorCode::InsufficientOutputAmount);
+        msg!("Swapped USDC for tenor tokens: {} USDC -> {} (fee: {})", 
+             usdc_amount, slots_out, fee_amount);
+        Ok(())
+    }
+
+    /// Buy an exact number of the pool's tenor tokens, paying at most `max_usdc_in`
+    pub fn swap_usdc_for_exact_slots(
+        ctx: Context<SwapUsdcForSlots>,
+        slots_out: u64,
+        max_usdc_in: u64,
+    ) -> Result<()> {
+        let pool = &ctx.accounts.pool;
+        require!(pool.is_active, ErrorCode::PoolInactive);
+        require!(slots_out > 0, ErrorCode::InvalidAmount);
+        
+        let (usdc_amount, fee_amount) =
+            pool_swap_exact_out(pool.token_a_amount, pool.token_b_amount, slots_out, pool.fee_rate)?;
+        require!(usdc_amount <= max_usdc_in, ErrorCode::SlippageExceeded);
+        
+        let accounts = &ctx.accounts;
+        pool_swap_transfers(
+            &accounts.token_program,
+            &accounts.user,
+            &accounts.pool_authority,
+            &pool.key(),
+            ctx.bumps.pool_authority,
+            (&accounts.user_token_a, &accounts.token_a_vault, usdc_amount),
+            (&accounts.token_b_vault, &accounts.user_token_b, slots_out),
+        )?;
         
-        // Update pool amounts
+        let pool = &mut ctx.accounts.pool;
         pool.token_a_amount = pool.token_a_amount.checked_add(usdc_amount)
             .ok_or(ErrorCode::MathOverflow)?;
         pool.token_b_amount = pool.token_b_amount.checked_sub(slots_out)
             .ok_or(ErrorCode::MathOverflow)?;
         
-        msg!("Swapped USDC for slots: {} USDC -> {} slots (fee: {})", 
+        msg!("Swapped USDC for exact tenor tokens: {} USDC -> {} (fee: {})", 
              usdc_amount, slots_out, fee_amount);
         Ok(())
     }
 
+    /// Sell the pool's tenor tokens for USDC (constant product, fee stays with LPs)
+    pub fn swap_slots_for_usdc(
+        ctx: Context<SwapSlotsForUsdc>,
+        slot_amount: u64,
+        min_usdc_out: u64,
+    ) -> Result<()> {
+        let pool = &ctx.accounts.pool;
+        require!(pool.is_active, ErrorCode::PoolInactive);
+        require!(slot_amount > 0, ErrorCode::InvalidAmount);
+        
+        let (usdc_out, fee_amount) =
+            pool_swap_exact_in(pool.token_b_amount, pool.token_a_amount, slot_amount, pool.fee_rate)?;
+        require!(usdc_out >= min_usdc_out, ErrorCode::InsufficientOutputAmount);
+        
+        let accounts = &ctx.accounts;
+        pool_swap_transfers(
+            &accounts.token_program,
+            &accounts.user,
+            &accounts.pool_authority,
+            &pool.key(),
+            ctx.bumps.pool_authority,
+            (&accounts.user_token_b, &accounts.token_b_vault, slot_amount),
+            (&accounts.token_a_vault, &accounts.user_token_a, usdc_out),
+        )?;
+        
+        let pool = &mut ctx.accounts.pool;
+        pool.token_b_amount = pool.token_b_amount.checked_add(slot_amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        pool.token_a_amount = pool.token_a_amount.checked_sub(usdc_out)
+            .ok_or(ErrorCode::MathOverflow)?;
+        
+        msg!("Swapped tenor tokens for USDC: {} -> {} USDC (fee: {} tenor tokens)", 
+             slot_amount, usdc_out, fee_amount);
+        Ok(())
+    }
+
+    /// Receive an exact amount of USDC for the pool's tenor tokens, selling at most `max_slots_in`
+    pub fn swap_slots_for_exact_usdc(
+        ctx: Context<SwapSlotsForUsdc>,
+        usdc_out: u64,
+        max_slots_in: u64,
+    ) -> Result<()> {
+        let pool = &ctx.accounts.pool;
+        require!(pool.is_active, ErrorCode::PoolInactive);
+        require!(usdc_out > 0, ErrorCode::InvalidAmount);
+        
+        let (slot_amount, fee_amount) =
+            pool_swap_exact_out(pool.token_b_amount, pool.token_a_amount, usdc_out, pool.fee_rate)?;
+        require!(slot_amount <= max_slots_in, ErrorCode::SlippageExceeded);
+        
+        let accounts = &ctx.accounts;
+        pool_swap_transfers(
+            &accounts.token_program,
+            &accounts.user,
+            &accounts.pool_authority,
+            &pool.key(),
+            ctx.bumps.pool_authority,
+            (&accounts.user_token_b, &accounts.token_b_vault, slot_amount),
+            (&accounts.token_a_vault, &accounts.user_token_a, usdc_out),
+        )?;
+        
+        let pool = &mut ctx.accounts.pool;
+        pool.token_b_amount = pool.token_b_amount.checked_add(slot_amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        pool.token_a_amount = pool.token_a_amount.checked_sub(usdc_out)
+            .ok_or(ErrorCode::MathOverflow)?;
+        
+        msg!("Swapped tenor tokens for exact USDC: {} -> {} USDC (fee: {} tenor tokens)", 
+             slot_amount, usdc_out, fee_amount);
+        Ok(())
+    }
+
     /// Initialize CADEN governance system
     pub fn init_governance(ctx: Context<InitGovernance>) -> Result<()> {
         let governance = &mut ctx.accounts.governance;
@@ -1083,8 +2920,9 @@ pub mod caden {
         governance.staking_apy = 1420; // 14.2% APY in basis points
         governance.proposal_count = 0;
         governance.quorum_threshold = 10_000_000 * 1_000_000; // 10M CADEN for quorum
-        governance.voting_period = 172800; // 1 day in slots (~400ms/slot)
-        governance.execution_delay = 43200; // 6 hours delay
+        governance.voting_period = 86_400; // 1 day
+        governance.execution_delay = 21_600; // 6 hours delay
+        governance.slot_royalty_bps = 0;
         governance.bump = ctx.bumps.governance;
         
         msg!("CADEN Governance initialized: 1B supply, governance proposals enabled");
@@ -1121,8 +2959,8 @@ pub mod caden {
         proposal.total_votes = 0;
         proposal.status = ProposalStatus::Active;
         proposal.created_slot = clock.slot;
-        proposal.voting_ends_slot = clock.slot + governance.voting_period;
-        proposal.execution_slot = 0;
+        proposal.voting_ends_at = clock.unix_timestamp + governance.voting_period;
+        proposal.execution_at = 0;
         proposal.executed = false;
         proposal.cancelled = false;
         proposal.bump = ctx.bumps.proposal;
@@ -1146,7 +2984,7 @@ pub mod caden {
         
         // Validate proposal is active
         require!(proposal.status == ProposalStatus::Active, ErrorCode::ProposalNotActive);
-        require!(clock.slot < proposal.voting_ends_slot, ErrorCode::VotingPeriodEnded);
+        require!(clock.unix_timestamp < proposal.voting_ends_at, ErrorCode::VotingPeriodEnded);
         
         // Validate voter has staked CADEN
         let vote_power = staking_position.caden_staked;
@@ -1186,7 +3024,7 @@ pub mod caden {
         let clock = Clock::get()?;
         
         // Validate voting period has ended
-        require!(clock.slot >= proposal.voting_ends_slot, ErrorCode::VotingPeriodNotEnded);
+        require!(clock.unix_timestamp >= proposal.voting_ends_at, ErrorCode::VotingPeriodNotEnded);
         require!(proposal.status == ProposalStatus::Active, ErrorCode::ProposalNotActive);
         
         // Check if quorum reached
@@ -1199,7 +3037,7 @@ pub mod caden {
         // Check if passed
         if proposal.votes_for > proposal.votes_against {
             proposal.status = ProposalStatus::Passed;
-            proposal.execution_slot = clock.slot + governance.execution_delay;
+            proposal.execution_at = clock.unix_timestamp + governance.execution_delay;
             msg!("Proposal #{} passed: {} for, {} against", 
                  proposal.proposal_id, proposal.votes_for / 1000000, proposal.votes_against / 1000000);
         } else {
@@ -1219,7 +3057,7 @@ pub mod caden {
         // Validate proposal can be executed
         require!(proposal.status == ProposalStatus::Passed, ErrorCode::ProposalNotPassed);
         require!(!proposal.executed, ErrorCode::ProposalAlreadyExecuted);
-        require!(clock.slot >= proposal.execution_slot, ErrorCode::ExecutionDelayNotMet);
+        require!(clock.unix_timestamp >= proposal.execution_at, ErrorCode::ExecutionDelayNotMet);
         
         // Execute proposal based on type
         match proposal.proposal_type {
@@ -1478,10 +3316,22 @@ pub mod caden {
 pub struct Market {
     pub t0_price: u64,      // T+0 settlement price
     pub t2_price: u64,      // T+2 settlement price (set at settlement)
-    pub expiry_slot: u64,   // Slot when market expires
+    pub opened_slot: u64,   // Slot when market opened (reference only)
     pub status: MarketStatus,
     pub usdc_vault: Pubkey, // USDC token account for collateral
     pub bump: u8,
+    pub asset_symbol: String,  // Underlying asset (BTC, AAPL, etc.)
+    pub asset_type: AssetType, // Underlying asset type
+    pub entry_price_mode: PriceMode,       // Price used for t0_price / position entry
+    pub liquidation_price_mode: PriceMode, // Price used to check position health
+    pub settlement_price_mode: PriceMode,  // Price used for t2_price at expiry
+    pub expiry_timestamp: i64,             // T+N business-day deadline (unix time)
+    pub settlement_days: u64,              // N in T+N, in business days
+}
+
+impl Market {
+    // discriminator + prices and opened slot + status + vault + bump + symbol + type + price modes + expiry + days
+    pub const SPACE: usize = 8 + 8 * 3 + 1 + 32 + 1 + (4 + 10) + 1 + 3 + 8 + 8;
 }
 
 #[account]
@@ -1503,14 +3353,150 @@ pub struct OracleMock {
     pub admin: Pubkey,      // Admin who can update prices
     pub price: u64,         // Current price (for Bitcoin/primary asset)
     pub updated_slot: u64,  // Last update slot
+    pub updated_by: Pubkey, // Admin or updater that wrote the price
+    pub max_move_bps_per_update: u16, // Max price move per update (0 = unlimited)
+    pub max_move_bps_per_slot: u16,   // Max price move per elapsed slot (0 = unlimited)
+    pub override_delay_secs: u64,     // Seconds before a single-authority price override can execute
+    pub asset_symbol: String,         // The single asset this mock prices
+    pub asset_type: AssetType,        // Type of that asset
+}
+
+#[account]
+pub struct OracleConfig {
+    pub admin: Pubkey,            // Admin who can switch price sources
+    pub mode: OracleMode,         // Which price source drives trading
+    pub max_staleness_slots: u64, // Max age of a price in slots (0 = unchecked)
+    pub bump: u8,                 // PDA bump seed
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub enum OracleMode {
+    Mock,         // OracleMock (localnet builds only)
+    MultiAsset,   // MultiAssetOracle admin prices
+    Pyth,         // Pyth column of the OracleAggregator
+    Switchboard,  // Switchboard column of the OracleAggregator
+    Aggregated,   // Median of all OracleAggregator sources
 }
 
 #[account]
+pub struct OracleUpdater {
+    pub oracle: Pubkey,               // Oracle account this role applies to
+    pub updater: Pubkey,              // Hot key allowed to push prices
+    pub asset_type_mask: u8,          // Bitmask of AssetTypes the updater may write
+    pub asset_symbols: Vec<String>,   // Symbols the updater may write (empty = any)
+    pub added_by: Pubkey,             // Admin or governance authority that added the role
+    pub added_slot: u64,              // Slot when the role was added
+    pub bump: u8,                     // PDA bump seed
+}
+
+#[account]
+pub struct PriceOverride {
+    pub oracle: Pubkey,               // Oracle account the override writes to
+    pub asset_symbol: String,         // Asset being overridden (must be OracleMock's asset)
+    pub asset_type: AssetType,
+    pub price: u64,                   // Price to write, exempt from move limits
+    pub proposer: Pubkey,             // Authority that proposed the override
+    pub proposed_slot: u64,           // Slot the override was proposed (reference only)
+    pub executable_timestamp: i64,    // Unix time after which it can execute without co-approval
+    pub admin_approved: bool,         // Oracle admin signed off
+    pub governance_approved: bool,    // Governance admin signed off
+    pub bump: u8,                     // PDA bump seed
+}
+
+#[account]
+pub struct PricePublisher {
+    pub publisher: Pubkey,            // Ed25519 key that signs off-chain price payloads
+    pub source: PriceSource,          // External source this publisher reports for
+    pub max_staleness_secs: i64,      // Oldest attestation accepted, relative to the cluster clock
+    pub asset_type_mask: u8,          // Bitmask of AssetTypes the publisher may attest
+    pub asset_symbols: Vec<String>,   // Symbols the publisher may attest (empty = any)
+    pub last_timestamp: i64,          // Timestamp of the last accepted attestation
+    pub last_nonce: u64,              // Nonce of the last accepted attestation
+    pub is_active: bool,              // Whether attestations are currently accepted
+    pub added_by: Pubkey,             // Admin or governance authority that registered the key
+    pub added_slot: u64,              // Slot when the publisher was (re)registered
+    pub bump: u8,                     // PDA bump seed
+}
+
+/// Off-chain price payload signed by a registered publisher
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PriceAttestation {
+    pub asset_symbol: String,
+    pub asset_type: AssetType,
+    pub price: u64,                   // Price with 6 decimals
+    pub confidence: u64,
+    pub source: PriceSource,
+    pub timestamp: i64,               // Unix time the publisher observed the price
+    pub nonce: u64,                   // Strictly increasing per publisher
+}
+
+#[account]
+pub struct PriceAccumulator {
+    pub asset_symbol: String,           // Asset symbol
+    pub asset_type: AssetType,          // Asset type
+    pub last_price: u64,                // Most recent oracle write
+    pub last_slot: u64,                 // Slot of the most recent write
+    pub cumulative_price: u128,         // Sum of price * slots held
+    pub window_start_slot: u64,         // Start of the current TWAP window
+    pub window_start_cumulative: u128,  // cumulative_price at window start
+    pub twap_price: u64,                // TWAP of the last completed window
+    pub twap_window_slots: u64,         // TWAP window length in slots
+    pub ema_price: u64,                 // Time-weighted EMA
+    pub ema_period_slots: u64,          // EMA period in slots
+    pub bump: u8,                       // PDA bump seed
+}
+
+impl PriceAccumulator {
+    // discriminator + symbol + type + last price/slot + cumulative + window start/cumulative + TWAP + EMA + bump
+    pub const SPACE: usize = 8 + (4 + 10) + 1 + 8 + 8 + 16 + 8 + 16 + 8 + 8 + 8 + 8 + 1;
+}
+
+#[account(zero_copy)]
+pub struct PriceHistory {
+    pub asset_symbol: [u8; SYMBOL_LEN],                    // Asset symbol (zero padded)
+    pub head: u32,                                         // Next write index
+    pub len: u32,                                          // Number of valid entries
+    pub asset_type: u8,                                    // AssetType as u8
+    pub bump: u8,                                          // PDA bump seed
+    pub _padding: [u8; 6],
+    pub entries: [PriceHistoryEntry; PRICE_HISTORY_LEN],   // Ring buffer of observations
+}
+
+#[zero_copy]
+pub struct PriceHistoryEntry {
+    pub slot: u64,          // Slot the price was written
+    pub price: u64,         // Price in 6 decimals
+    pub confidence: u64,    // Confidence interval
+    pub source: u8,         // PriceSource as u8
+    pub _padding: [u8; 7],
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct HistoricalPrice {
+    pub slot: u64,
+    pub price: u64,
+    pub confidence: u64,
+    pub source: PriceSource,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub enum PriceMode {
+    Spot,  // Latest oracle price
+    Twap,  // Time-weighted average price
+    Ema,   // Exponential moving average
+}
+
+// Followed in the account data by an AssetPrice table filling the rest of the account (see price_table)
+#[account(zero_copy)]
 pub struct MultiAssetOracle {
     pub admin: Pubkey,                    // Admin who can update prices
-    pub asset_prices: Vec<AssetPrice>,    // Prices for different assets
     pub updated_slot: u64,               // Last update slot
+    pub override_delay_secs: u64,        // Seconds before a single-authority price override can execute
+    pub len: u32,                        // Number of assets stored
+    pub max_move_bps_per_update: u16,    // Max price move per update (0 = unlimited)
+    pub max_move_bps_per_slot: u16,      // Max price move per elapsed slot (0 = unlimited)
     pub bump: u8,                        // PDA bump seed
+    pub _padding: [u8; 7],
 }
 
 #[account]
@@ -1520,26 +3506,46 @@ pub struct SettlementSlotPool {
     pub asset_type: AssetType,           // Asset type (Stock, Crypto, etc.)
     pub settlement_time: u64,            // Settlement timing (0=T+0, 1=T+1, etc.)
     pub token_a_mint: Pubkey,            // USDC mint (always token A)
-    pub token_b_mint: Pubkey,            // Settlement slot NFT mint (token B)
+    pub token_b_mint: Pubkey,            // Slot-backed tenor token mint (token B, 0 decimals)
     pub token_a_vault: Pubkey,           // USDC vault
-    pub token_b_vault: Pubkey,           // Settlement slot NFT vault
+    pub token_b_vault: Pubkey,           // Tenor token vault
     pub token_a_amount: u64,             // Current USDC amount in pool
-    pub token_b_amount: u64,             // Current settlement slot NFT amount in pool
+    pub token_b_amount: u64,             // Current tenor token amount in pool
     pub fee_rate: u16,                   // Fee rate in basis points (e.g., 30 = 0.3%)
     pub pool_authority: Pubkey,          // Pool authority (program)
+    pub lp_mint: Pubkey,                 // LP token mint (authority: pool_authority)
+    pub lp_supply: u64,                  // Outstanding LP tokens
+    pub creator: Pubkey,                 // Account that created the pool
     pub created_slot: u64,               // Slot when pool was created
     pub is_active: bool,                 // Whether pool is active
     pub bump: u8,                        // PDA bump seed
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+#[account]
+pub struct PoolRegistry {
+    pub pools: Vec<RegisteredPool>,      // Canonical pool per (asset, asset type, T+N)
+    pub bump: u8,                        // PDA bump seed
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct RegisteredPool {
+    pub asset_symbol: String,
+    pub asset_type: AssetType,
+    pub settlement_time: u64,            // N in T+N
+    pub pool: Pubkey,                    // SettlementSlotPool address
+}
+
+#[zero_copy]
 pub struct AssetPrice {
-    pub asset_symbol: String,    // AAPL, TSLA, BTC, etc.
-    pub asset_type: AssetType,   // Stock, Crypto, Bond, etc.
+    pub asset_symbol: [u8; SYMBOL_LEN], // AAPL, TSLA, BTC, etc. (zero padded)
     pub price: u64,             // Price in 6 decimals
     pub last_updated: u64,      // Last update slot
-    pub source: PriceSource,    // Price feed source
     pub confidence: u64,        // Confidence interval
+    pub updated_by: Pubkey,     // Admin or updater that wrote the price
+    pub asset_type: u8,         // AssetType as u8
+    pub source: u8,             // PriceSource as u8
+    pub occupied: u8,           // Whether this slot holds an asset
+    pub _padding: [u8; 5],
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
@@ -1554,27 +3560,52 @@ pub enum PriceSource {
     Aggregated,     // Aggregated from multiple sources
 }
 
-#[account]
+impl PriceSource {
+    pub fn from_u8(value: u8) -> Self {
+        match value {
+            0 => PriceSource::Pyth,
+            1 => PriceSource::Switchboard,
+            2 => PriceSource::Chainlink,
+            3 => PriceSource::CoinGecko,
+            4 => PriceSource::TwelveData,
+            5 => PriceSource::Binance,
+            6 => PriceSource::Manual,
+            _ => PriceSource::Aggregated,
+        }
+    }
+}
+
+// Followed in the account data by a PriceFeed table filling the rest of the account (see price_table)
+#[account(zero_copy)]
 pub struct OracleAggregator {
     pub admin: Pubkey,                          // Admin who can manage sources
-    pub enabled_sources: Vec<PriceSource>,      // Active price sources
-    pub price_feeds: Vec<PriceFeed>,            // All price feeds
     pub update_frequency: u64,                  // Update frequency in slots
     pub last_crank_slot: u64,                   // Last crank slot
+    pub override_delay_secs: u64,               // Seconds before a single-authority price override can execute
+    pub len: u32,                               // Number of feeds stored
     pub deviation_threshold: u16,               // Price deviation threshold (bps)
+    pub enabled_sources: u16,                   // Bitmask of active PriceSources
+    pub max_move_bps_per_update: u16,           // Max move of any feed column or aggregated price per update (0 = unlimited)
+    pub max_move_bps_per_slot: u16,             // Max move of any feed column or aggregated price per elapsed slot (0 = unlimited)
     pub bump: u8,                               // PDA bump seed
+    pub _padding: [u8; 3],
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+#[zero_copy]
 pub struct PriceFeed {
-    pub asset_symbol: String,           // Asset symbol
-    pub asset_type: AssetType,          // Asset type
+    pub asset_symbol: [u8; SYMBOL_LEN], // Asset symbol (zero padded)
     pub pyth_price: u64,               // Pyth price
     pub switchboard_price: u64,        // Switchboard price
     pub external_price: u64,           // External API price
     pub aggregated_price: u64,         // Final aggregated price
     pub last_updated: u64,             // Last update slot
-    pub is_stale: bool,                // Whether price is stale
+    pub updated_by: Pubkey,            // Signer of the last update
+    pub pyth_account: Pubkey,          // Pyth price account pinned for this asset (default = none)
+    pub switchboard_account: Pubkey,   // Switchboard aggregator pinned for this asset (default = none)
+    pub asset_type: u8,                // AssetType as u8
+    pub is_stale: u8,                  // Whether price is stale
+    pub occupied: u8,                  // Whether this slot holds a feed
+    pub _padding: [u8; 5],
 }
 
 #[account]
@@ -1588,8 +3619,8 @@ pub struct Governance {
     pub staking_apy: u16,        // Current staking APY in basis points
     pub proposal_count: u64,     // Total proposals created
     pub quorum_threshold: u64,   // Minimum votes for quorum (in CADEN)
-    pub voting_period: u64,      // Voting period in slots
-    pub execution_delay: u64,    // Delay before execution in slots
+    pub voting_period: i64,      // Voting period in seconds
+    pub execution_delay: i64,    // Delay before execution in seconds
     pub bump: u8,
 }
 
@@ -1605,8 +3636,8 @@ pub struct GovernanceProposal {
     pub total_votes: u64,            // Total votes cast
     pub status: ProposalStatus,      // Current status
     pub created_slot: u64,           // Creation slot
-    pub voting_ends_slot: u64,       // Voting end slot
-    pub execution_slot: u64,         // Execution slot (if passed)
+    pub voting_ends_at: i64,         // Voting end (unix time)
+    pub execution_at: i64,           // Earliest execution (unix time, if passed)
     pub executed: bool,              // Whether executed
     pub cancelled: bool,             // Whether cancelled
     pub bump: u8,                    // PDA bump seed
@@ -1651,16 +3682,61 @@ pub enum VoteChoice {
     Abstain,
 }
 
-#[account]
+#[account(zero_copy)]
 pub struct SpreadHeatmap {
-    pub spreads: [SpreadEntry; 100], // Fixed array of 100 entries
-    pub bump: u8,
+    pub market: Pubkey,                             // Market that initialized this heatmap
+    pub asset_symbol: [u8; SYMBOL_LEN],             // Asset this heatmap tracks (zero padded)
+    pub min_crank_interval_slots: u64,              // Minimum slots between cranks
+    pub last_crank_slot: u64,                       // Slot of the latest entry
+    pub head: u32,                                  // Next write index
+    pub len: u32,                                   // Number of valid entries
+    pub asset_type: u8,                             // AssetType as u8
+    pub bump: u8,                                   // PDA bump seed
+    pub _padding: [u8; 6],
+    pub spreads: [SpreadEntry; SPREAD_HISTORY_LEN], // Ring buffer of spread observations
+}
+
+// Asset rows form an open-addressed hash table keyed by (symbol, asset type) (see price_table)
+#[account(zero_copy)]
+pub struct TenorHeatmap {
+    pub last_crank_slot: u64,                          // Slot of the latest crank
+    pub len: u32,                                      // Number of asset rows in use
+    pub bump: u8,                                      // PDA bump seed
+    pub _padding: [u8; 3],
+    pub rows: [HeatmapRow; MAX_HEATMAP_ASSETS],        // One row per asset
+}
+
+// `init` allocates through a system program CPI, which caps new accounts at 10KB
+const _: () = assert!(
+    8 + std::mem::size_of::<TenorHeatmap>() <= anchor_lang::solana_program::entrypoint::MAX_PERMITTED_DATA_INCREASE
+);
+
+#[zero_copy]
+pub struct HeatmapRow {
+    pub asset_symbol: [u8; SYMBOL_LEN],                // Asset symbol (zero padded)
+    pub asset_type: u8,                                // AssetType as u8
+    pub occupied: u8,                                  // Non-zero once the row is in use
+    pub _padding: [u8; 6],
+    pub cells: [TenorCell; HEATMAP_TENORS],            // Indexed by N in T+N
+}
+
+#[zero_copy]
+pub struct TenorCell {
+    pub pool_price: u64,          // USDC per slot implied by the T+N pool's tenor token price (6 decimals)
+    pub pool_liquidity: u64,      // USDC reserves backing pool_price
+    pub market_price: u64,        // Entry price of an active market (T+0) or settlement price of a settled T+N market
+    pub updated_slot: u64,        // Slot of the latest update
+    pub updated_timestamp: i64,   // Unix time of the latest update
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+#[zero_copy]
 pub struct SpreadEntry {
-    pub bid: u64,
-    pub ask: u64,
+    pub slot: u64,          // Slot the spread was recorded
+    pub timestamp: i64,     // Unix time the spread was recorded
+    pub bid: u64,           // Live T+0 (oracle) price
+    pub ask: u64,           // Market T+2 price
+    pub spread_bps: i32,    // (ask - bid) / bid in basis points
+    pub _padding: [u8; 4],
 }
 
 #[account]
@@ -1711,8 +3787,9 @@ pub struct CadenGovernance {
     pub staking_apy: u16,        // Current staking APY in basis points
     pub proposal_count: u64,     // Total governance proposals created
     pub quorum_threshold: u64,   // Minimum votes for quorum (in CADEN)
-    pub voting_period: u64,      // Voting period in slots
-    pub execution_delay: u64,    // Delay before execution in slots
+    pub voting_period: i64,      // Voting period in seconds
+    pub execution_delay: i64,    // Delay before execution in seconds
+    pub slot_royalty_bps: u16,   // Protocol royalty on secondary settlement slot sales
     pub bump: u8,
 }
 
@@ -1732,15 +3809,165 @@ pub struct SettlementSlot {
     pub asset_type: AssetType,   // Type of asset (Stock, Crypto, Bond, etc.)
     pub settlement_time: u64,    // Settlement timing (0=T+0, 1=T+1, 2=T+2, etc.)
     pub slot_duration: u64,      // Duration of slot in days
-    pub owner: Pubkey,           // Current owner of the settlement slot
+    pub creator: Pubkey,         // Original minter; the current owner is whoever holds the token
+    pub mint: Pubkey,            // Supply-1 Token-2022 mint representing this slot
     pub mint_price: u64,         // Price paid to mint this slot
     pub created_slot: u64,       // Slot when this was created
-    pub expiry_slot: u64,        // Slot when this expires
+    pub created_timestamp: i64,  // Unix time when this was created
+    pub expiry_timestamp: i64,   // Unix time when this expires
+    pub notional_budget: u64,    // Bet notional this slot can settle instantly (0 = unlimited)
+    pub notional_used: u64,      // Bet notional settled with this slot so far
     pub is_tradable: bool,       // Whether this slot can be traded
     pub is_active: bool,         // Whether this slot is still active
     pub bump: u8,                // PDA bump seed
 }
 
+/// Terms of a settlement slot being issued, by mint or by auction
+pub struct SlotTerms {
+    pub slot_id: u64,
+    pub asset_symbol: String,
+    pub asset_type: AssetType,
+    pub settlement_time: u64,
+    pub slot_duration: u64,
+    pub mint_price: u64,
+    pub expiry_timestamp: i64,
+    pub notional_budget: u64,
+    pub creator: Pubkey,
+    pub bump: u8,
+}
+
+#[account]
+pub struct SlotPricing {
+    pub asset_symbol: String,    // Asset the curve prices
+    pub asset_type: AssetType,
+    pub settlement_time: u64,    // Tenor (N in T+N)
+    pub base_price: u64,         // USDC price of the first slot
+    pub price_step: u64,         // USDC added per outstanding slot (linear bonding curve)
+    pub max_supply: u64,         // Cap on outstanding slots (0 = uncapped; required for T+0)
+    pub notional_budget: u64,    // Bet notional each slot can settle instantly (0 = unlimited; required for T+0)
+    pub outstanding: u64,        // Slots minted and not yet retired
+    pub reserved: u64,           // Unsold auction lots held against max_supply (not priced by the curve)
+    pub total_minted: u64,       // Lifetime slots minted
+    pub total_revenue: u64,      // Lifetime USDC paid to the protocol
+    pub bump: u8,                // PDA bump seed
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SlotPricingParams {
+    pub base_price: u64,
+    pub price_step: u64,
+    pub max_supply: u64,
+    pub notional_budget: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct SlotAuctionParams {
+    pub total_slots: u64,
+    pub slot_duration: u64,
+    pub start_price: u64,
+    pub floor_price: u64,
+    pub decay_per_second: u64,
+    pub start_timestamp: i64,
+    pub end_timestamp: i64,
+    pub notional_budget: u64,
+}
+
+#[account]
+pub struct SlotAuction {
+    pub asset_symbol: String,    // Asset whose T+0 slots are being sold
+    pub asset_type: AssetType,
+    pub epoch: u64,              // Auction round for this asset
+    pub total_slots: u64,        // Slots offered
+    pub sold: u64,               // Slots sold so far
+    pub slot_duration: u64,      // Lifetime of each slot sold, in business days
+    pub start_price: u64,        // USDC price at start_timestamp
+    pub floor_price: u64,        // Lowest USDC price
+    pub decay_per_second: u64,   // USDC the price drops per second
+    pub start_timestamp: i64,    // Unix time the auction opens
+    pub end_timestamp: i64,      // Unix time the auction closes
+    pub notional_budget: u64,    // Bet notional each slot sold can settle instantly
+    pub revenue: u64,            // USDC raised
+    pub is_finalized: bool,      // Whether unsold slots have been burned
+    pub bump: u8,                // PDA bump seed
+}
+
+#[account]
+pub struct SlotRental {
+    pub settlement_slot: Pubkey, // Slot offered for rent
+    pub lender: Pubkey,          // Owner lending the slot
+    pub payment_account: Pubkey, // Lender's USDC account receiving rental fees
+    pub fee_per_day: u64,        // USDC fee per day of rental
+    pub max_days: u64,           // Longest rental the lender accepts
+    pub renter: Pubkey,          // Current or most recent renter
+    pub rented_until: i64,       // Unix time the current rental ends (0 if never rented)
+    pub total_earned: u64,       // Lifetime USDC earned by the lender
+    pub bump: u8,                // PDA bump seed
+}
+
+#[account]
+pub struct FractionVault {
+    pub settlement_slot: Pubkey,     // Slot locked in the vault
+    pub owner: Pubkey,               // Holder who fractionalized the slot; the vault's rent returns to them
+    pub fraction_mint: Pubkey,       // Fungible fraction token (0 decimals)
+    pub total_fractions: u64,        // Fractions minted when the slot was locked
+    pub fractions_burned: u64,       // Fractions burned to pay for instant settlements
+    pub notional_per_fraction: u64,  // Bet notional each fraction can settle
+    pub bump: u8,                    // PDA bump seed
+}
+
+#[account]
+pub struct TenorVault {
+    pub asset_symbol: String,        // Asset of every slot the vault holds
+    pub asset_type: AssetType,
+    pub settlement_time: u64,        // Tenor (T+N) of every slot the vault holds
+    pub tenor_mint: Pubkey,          // Fungible tenor token, one per wrapped slot (0 decimals)
+    pub wrapped: u64,                // Slots held; always equals the tenor token supply
+    pub bump: u8,                    // PDA bump seed
+}
+
+#[event]
+pub struct SlotUpgraded {
+    pub settlement_slot: Pubkey,
+    pub owner: Pubkey,
+    pub asset_symbol: String,
+    pub from_tenor: u64,
+    pub to_tenor: u64,
+    pub premium: u64,            // USDC paid to the protocol
+}
+
+#[account]
+pub struct SlotCounter {
+    pub next_slot_id: u64,       // Id assigned to the next minted settlement slot
+    pub bump: u8,                // PDA bump seed
+}
+
+#[account]
+pub struct SlotListing {
+    pub settlement_slot: Pubkey, // Slot being sold
+    pub seller: Pubkey,          // Holder who listed it; receives the proceeds
+    pub price: u64,              // Asking price in USDC
+    pub created_timestamp: i64,  // Unix time when listed
+    pub bump: u8,                // PDA bump seed
+}
+
+#[account]
+pub struct TradingSchedule {
+    pub asset_symbol: String,            // Asset the calendar applies to
+    pub asset_type: AssetType,
+    pub utc_offset_secs: i32,            // Exchange local time offset from UTC
+    pub sessions: [TradingSession; 7],   // Weekly sessions, Monday first
+    pub holidays: Vec<u32>,              // Closed days (local days since the unix epoch)
+    pub updated_slot: u64,               // Slot of the last change
+    pub bump: u8,                        // PDA bump seed
+}
+
+/// One day's session in exchange local time; open == close means closed all day
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug)]
+pub struct TradingSession {
+    pub open_minute: u16,   // Minutes after local midnight
+    pub close_minute: u16,  // Minutes after local midnight
+}
+
 #[account]
 pub struct Bet {
     pub owner: Pubkey,           // Owner of the bet
@@ -1787,11 +4014,12 @@ pub enum PositionSide {
 pub struct Initialize {}
 
 #[derive(Accounts)]
+#[instruction(asset_symbol: String, asset_type: AssetType)]
 pub struct InitMarket<'info> {
     #[account(
         init_if_needed,
         payer = user,
-        space = 8 + 32 + 32 + 8 + 8 + 8 + 1 + 32 + 1,
+        space = Market::SPACE,
         seeds = [b"market"],
         bump
     )]
@@ -1813,7 +4041,27 @@ pub struct InitMarket<'info> {
     /// CHECK: USDC mint address (passed as parameter)
     pub usdc_mint: AccountInfo<'info>,
     
-    pub oracle_mock: Account<'info, OracleMock>,
+    #[account(
+        seeds = [b"oracle_config"],
+        bump = oracle_config.bump
+    )]
+    pub oracle_config: Account<'info, OracleConfig>,
+    
+    /// CHECK: Price account for oracle_config.mode - validated in oracle::load_price
+    pub price_account: AccountInfo<'info>,
+    
+    #[account(
+        seeds = [b"price_accumulator", asset_symbol.as_bytes(), &[asset_type as u8]],
+        bump = price_accumulator.bump
+    )]
+    pub price_accumulator: Account<'info, PriceAccumulator>,
+    
+    /// CHECK: Trading schedule PDA - read with calendar::load_schedule
+    #[account(
+        seeds = [b"trading_schedule", asset_symbol.as_bytes(), &[asset_type as u8]],
+        bump
+    )]
+    pub trading_schedule: UncheckedAccount<'info>,
     
     pub token_program: Program<'info, Token>,  // Changed from Token2022 to Token
     pub system_program: Program<'info, System>,
@@ -1825,7 +4073,7 @@ pub struct InitOracle<'info> {
     #[account(
         init_if_needed,  // Changed from init to init_if_needed
         payer = admin,
-        space = 8 + 32 + 8 + 8,
+        space = 8 + 32 + 8 + 8 + 32 + 2 + 2 + 8 + (4 + 10) + 1,
         seeds = [b"oracle"],
         bump
     )]
@@ -1838,15 +4086,15 @@ pub struct InitOracle<'info> {
 }
 
 #[derive(Accounts)]
-pub struct InitHeatmap<'info> {
+pub struct InitOracleConfig<'info> {
     #[account(
-        init_if_needed,
+        init,
         payer = admin,
-        space = 1650, // Match existing account size
-        seeds = [b"heatmap"],
+        space = 8 + 32 + 1 + 8 + 1,
+        seeds = [b"oracle_config"],
         bump
     )]
-    pub heatmap: Account<'info, SpreadHeatmap>,
+    pub oracle_config: Account<'info, OracleConfig>,
     
     #[account(mut)]
     pub admin: Signer<'info>,
@@ -1855,646 +4103,2292 @@ pub struct InitHeatmap<'info> {
 }
 
 #[derive(Accounts)]
-pub struct CrankHeatmap<'info> {
+pub struct SetOracleConfig<'info> {
     #[account(
         mut,
-        seeds = [b"heatmap"],
-        bump
-    )]
-    pub heatmap: Account<'info, SpreadHeatmap>,
-    
-    #[account(
-        seeds = [b"market"],
-        bump = market.bump
+        seeds = [b"oracle_config"],
+        bump = oracle_config.bump,
+        constraint = oracle_config.admin == admin.key() @ ErrorCode::Unauthorized
     )]
-    pub market: Account<'info, Market>,
+    pub oracle_config: Account<'info, OracleConfig>,
     
-    pub oracle_mock: Account<'info, OracleMock>,
+    pub admin: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct InitAmmPool<'info> {
-    #[account(
-        init_if_needed,
-        payer = admin,
-        space = 8 + 32 + 32 + 32 + 32 + 32 + 32 + 8 + 8 + 8 + 2 + 2 + 8 + 8 + 1,
-        seeds = [b"amm_pool"],
-        bump
-    )]
-    pub pool: Account<'info, AmmPool>,
-    
-    pub long_mint: Account<'info, Mint>,
-    pub short_mint: Account<'info, Mint>,
-    
-    #[account(
-        init_if_needed,
-        payer = admin,
-        mint::decimals = 6,
-        mint::authority = pool,
-        seeds = [b"lp_mint"],
-        bump
-    )]
-    pub lp_mint: Account<'info, Mint>,
+#[instruction(updater: Pubkey)]
+pub struct AddOracleUpdater<'info> {
+    /// CHECK: OracleMock, MultiAssetOracle or OracleAggregator - validated in oracle::oracle_admin
+    pub oracle: AccountInfo<'info>,
     
     #[account(
         init_if_needed,
-        payer = admin,
-        token::mint = long_mint,
-        token::authority = pool,
-        seeds = [b"long_vault"],
+        payer = authority,
+        space = 8 + 32 + 32 + 1 + 4 + MAX_UPDATER_ASSETS * (4 + 10) + 32 + 8 + 1, // OracleUpdater size
+        seeds = [b"oracle_updater", oracle.key().as_ref(), updater.as_ref()],
         bump
     )]
-    pub long_vault: Account<'info, TokenAccount>,
+    pub updater_role: Account<'info, OracleUpdater>,
     
     #[account(
-        init_if_needed,
-        payer = admin,
-        token::mint = short_mint,
-        token::authority = pool,
-        seeds = [b"short_vault"],
-        bump
+        seeds = [b"governance"],
+        bump = governance.bump
     )]
-    pub short_vault: Account<'info, TokenAccount>,
+    pub governance: Option<Account<'info, CadenGovernance>>,
     
     #[account(mut)]
-    pub admin: Signer<'info>,
+    pub authority: Signer<'info>,
     
-    pub token_program: Program<'info, Token>,  // Changed from Token2022 to Token
     pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
 }
 
 #[derive(Accounts)]
-pub struct AddLiquidity<'info> {
+pub struct RemoveOracleUpdater<'info> {
+    /// CHECK: OracleMock, MultiAssetOracle or OracleAggregator - validated in oracle::oracle_admin
+    pub oracle: AccountInfo<'info>,
+    
     #[account(
         mut,
-        seeds = [b"amm_pool"],
-        bump = pool.bump
+        seeds = [b"oracle_updater", oracle.key().as_ref(), updater_role.updater.as_ref()],
+        bump = updater_role.bump,
+        close = authority
     )]
-    pub pool: Account<'info, AmmPool>,
+    pub updater_role: Account<'info, OracleUpdater>,
     
     #[account(
-        init_if_needed,
-        payer = user,
-        space = 8 + 32 + 8 + 8 + 8 + 1,
-        seeds = [b"lp_position", user.key().as_ref()],
-        bump
+        seeds = [b"governance"],
+        bump = governance.bump
     )]
-    pub lp_position: Account<'info, LpPosition>,
+    pub governance: Option<Account<'info, CadenGovernance>>,
     
     #[account(mut)]
-    pub long_vault: Account<'info, TokenAccount>,
-    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetOracleMoveLimits<'info> {
+    /// CHECK: OracleMock, MultiAssetOracle or OracleAggregator - validated in oracle::set_move_limits
     #[account(mut)]
-    pub short_vault: Account<'info, TokenAccount>,
+    pub oracle: AccountInfo<'info>,
     
-    #[account(mut)]
-    pub lp_mint: Account<'info, Mint>,
+    #[account(
+        seeds = [b"governance"],
+        bump = governance.bump
+    )]
+    pub governance: Account<'info, CadenGovernance>,
     
-    #[account(mut)]
-    pub user_long_account: Account<'info, TokenAccount>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(asset_symbol: String, asset_type: AssetType)]
+pub struct ProposePriceOverride<'info> {
+    /// CHECK: OracleMock, MultiAssetOracle or OracleAggregator - validated in oracle::oracle_admin
+    pub oracle: AccountInfo<'info>,
     
-    #[account(mut)]
-    pub user_short_account: Account<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 32 + 4 + 10 + 1 + 8 + 32 + 8 + 8 + 1 + 1 + 1, // PriceOverride size
+        seeds = [b"price_override", oracle.key().as_ref(), asset_symbol.as_bytes(), &[asset_type as u8]],
+        bump
+    )]
+    pub price_override: Account<'info, PriceOverride>,
     
-    #[account(mut)]
-    pub user_lp_account: Account<'info, TokenAccount>,
+    #[account(
+        seeds = [b"governance"],
+        bump = governance.bump
+    )]
+    pub governance: Option<Account<'info, CadenGovernance>>,
     
     #[account(mut)]
-    pub user: Signer<'info>,
+    pub authority: Signer<'info>,
     
-    pub token_program: Program<'info, Token>,  // Changed from Token2022 to Token
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct SwapCfdTokens<'info> {
+pub struct ApprovePriceOverride<'info> {
+    /// CHECK: OracleMock, MultiAssetOracle or OracleAggregator - validated in oracle::oracle_admin
+    #[account(address = price_override.oracle)]
+    pub oracle: AccountInfo<'info>,
+    
     #[account(
         mut,
-        seeds = [b"amm_pool"],
-        bump = pool.bump
+        seeds = [
+            b"price_override",
+            price_override.oracle.as_ref(),
+            price_override.asset_symbol.as_bytes(),
+            &[price_override.asset_type as u8]
+        ],
+        bump = price_override.bump
     )]
-    pub pool: Account<'info, AmmPool>,
-    
-    #[account(mut)]
-    pub vault_in: Account<'info, TokenAccount>,
-    
-    #[account(mut)]
-    pub vault_out: Account<'info, TokenAccount>,
-    
-    #[account(mut)]
-    pub user_token_in: Account<'info, TokenAccount>,
-    
-    #[account(mut)]
-    pub user_token_out: Account<'info, TokenAccount>,
+    pub price_override: Account<'info, PriceOverride>,
     
-    #[account(mut)]
-    pub user: Signer<'info>,
+    #[account(
+        seeds = [b"governance"],
+        bump = governance.bump
+    )]
+    pub governance: Option<Account<'info, CadenGovernance>>,
     
-    pub token_program: Program<'info, Token>,  // Changed from Token2022 to Token
+    pub authority: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct InitGovernance<'info> {
+pub struct ExecutePriceOverride<'info> {
+    /// CHECK: OracleMock, MultiAssetOracle or OracleAggregator - validated in oracle::write_override_price
+    #[account(mut, address = price_override.oracle)]
+    pub oracle: AccountInfo<'info>,
+    
     #[account(
-        init_if_needed,
-        payer = admin,
-        space = 8 + 32 + 32 + 32 + 8 + 8 + 32 + 32 + 8 + 8 + 2 + 8 + 8 + 8 + 8 + 1,
-        seeds = [b"governance"],
-        bump
+        mut,
+        seeds = [
+            b"price_override",
+            price_override.oracle.as_ref(),
+            price_override.asset_symbol.as_bytes(),
+            &[price_override.asset_type as u8]
+        ],
+        bump = price_override.bump,
+        close = proposer
     )]
-    pub governance: Account<'info, CadenGovernance>,
-    
-    pub caden_mint: Account<'info, Mint>,
+    pub price_override: Account<'info, PriceOverride>,
     
     #[account(
-        init_if_needed,
-        payer = admin,
-        mint::decimals = 6,
-        mint::authority = governance,
-        seeds = [b"staked_caden_mint"],
-        bump
+        seeds = [b"oracle_config"],
+        bump = oracle_config.bump
     )]
-    pub staked_caden_mint: Account<'info, Mint>,
+    pub oracle_config: Account<'info, OracleConfig>,
     
     #[account(
         init_if_needed,
-        payer = admin,
-        token::mint = usdc_mint,
-        token::authority = governance,
-        seeds = [b"protocol_fee_vault"],
+        payer = payer,
+        space = PriceAccumulator::SPACE,
+        seeds = [b"price_accumulator", price_override.asset_symbol.as_bytes(), &[price_override.asset_type as u8]],
         bump
     )]
-    pub protocol_fee_vault: Account<'info, TokenAccount>,
+    pub price_accumulator: Account<'info, PriceAccumulator>,
     
     #[account(
-        init_if_needed,
-        payer = admin,
-        token::mint = usdc_mint,
-        token::authority = governance,
-        seeds = [b"buyback_vault"],
-        bump
+        mut,
+        seeds = [b"price_history", price_override.asset_symbol.as_bytes(), &[price_override.asset_type as u8]],
+        bump = price_history.load()?.bump
     )]
-    pub buyback_vault: Account<'info, TokenAccount>,
+    pub price_history: AccountLoader<'info, PriceHistory>,
     
-    pub usdc_mint: Account<'info, Mint>,
+    /// CHECK: Receives the override account's rent
+    #[account(mut, address = price_override.proposer)]
+    pub proposer: AccountInfo<'info>,
     
     #[account(mut)]
-    pub admin: Signer<'info>,
+    pub payer: Signer<'info>,
     
-    pub token_program: Program<'info, Token>,  // Changed from Token2022 to Token
     pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
 }
 
 #[derive(Accounts)]
-pub struct StakeCaden<'info> {
+pub struct CancelPriceOverride<'info> {
+    /// CHECK: OracleMock, MultiAssetOracle or OracleAggregator - validated in oracle::oracle_admin
+    #[account(address = price_override.oracle)]
+    pub oracle: AccountInfo<'info>,
+    
     #[account(
         mut,
+        seeds = [
+            b"price_override",
+            price_override.oracle.as_ref(),
+            price_override.asset_symbol.as_bytes(),
+            &[price_override.asset_type as u8]
+        ],
+        bump = price_override.bump,
+        close = proposer
+    )]
+    pub price_override: Account<'info, PriceOverride>,
+    
+    #[account(
         seeds = [b"governance"],
         bump = governance.bump
     )]
-    pub governance: Account<'info, CadenGovernance>,
+    pub governance: Option<Account<'info, CadenGovernance>>,
+    
+    /// CHECK: Receives the override account's rent
+    #[account(mut, address = price_override.proposer)]
+    pub proposer: AccountInfo<'info>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(asset_symbol: String, asset_type: AssetType)]
+pub struct SetTradingSchedule<'info> {
+    #[account(
+        seeds = [b"oracle_config"],
+        bump = oracle_config.bump,
+        constraint = oracle_config.admin == admin.key() @ ErrorCode::Unauthorized
+    )]
+    pub oracle_config: Account<'info, OracleConfig>,
     
     #[account(
         init_if_needed,
-        payer = user,
-        space = 8 + 32 + 8 + 8 + 8 + 8 + 8 + 1,
-        seeds = [b"staking_position", user.key().as_ref()],
+        payer = admin,
+        // discriminator + symbol + asset_type + utc_offset_secs (i32) + sessions (fixed array,
+        // no length prefix) + holidays (length prefix + days) + updated_slot + bump
+        space = 8 + (4 + 10) + 1 + 4 + 7 * (2 + 2) + (4 + MAX_HOLIDAYS * 4) + 8 + 1,
+        seeds = [b"trading_schedule", asset_symbol.as_bytes(), &[asset_type as u8]],
         bump
     )]
-    pub staking_position: Account<'info, StakingPosition>,
-    
-    #[account(mut)]
-    pub caden_mint: Account<'info, Mint>,
-    
-    #[account(mut)]
-    pub staked_caden_mint: Account<'info, Mint>,
+    pub trading_schedule: Account<'info, TradingSchedule>,
     
     #[account(mut)]
-    pub governance_caden_vault: Account<'info, TokenAccount>,
+    pub admin: Signer<'info>,
     
-    #[account(mut)]
-    pub user_caden_account: Account<'info, TokenAccount>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(asset_symbol: String, asset_type: AssetType)]
+pub struct ConfigurePriceAccumulator<'info> {
+    #[account(
+        seeds = [b"oracle_config"],
+        bump = oracle_config.bump,
+        constraint = oracle_config.admin == admin.key() @ ErrorCode::Unauthorized
+    )]
+    pub oracle_config: Account<'info, OracleConfig>,
     
-    #[account(mut)]
-    pub user_stk_caden_account: Account<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = PriceAccumulator::SPACE,
+        seeds = [b"price_accumulator", asset_symbol.as_bytes(), &[asset_type as u8]],
+        bump
+    )]
+    pub price_accumulator: Account<'info, PriceAccumulator>,
     
     #[account(mut)]
-    pub user: Signer<'info>,
+    pub admin: Signer<'info>,
     
-    pub clock: Sysvar<'info, Clock>,
-    pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct ClaimFees<'info> {
+#[instruction(asset_symbol: String, asset_type: AssetType)]
+pub struct InitPriceHistory<'info> {
+    #[account(
+        seeds = [b"oracle_config"],
+        bump = oracle_config.bump,
+        constraint = oracle_config.admin == admin.key() @ ErrorCode::Unauthorized
+    )]
+    pub oracle_config: Account<'info, OracleConfig>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + std::mem::size_of::<PriceHistory>(),
+        seeds = [b"price_history", asset_symbol.as_bytes(), &[asset_type as u8]],
+        bump
+    )]
+    pub price_history: AccountLoader<'info, PriceHistory>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(asset_symbol: String, asset_type: AssetType)]
+pub struct GetPriceAtSlot<'info> {
+    #[account(
+        seeds = [b"price_history", asset_symbol.as_bytes(), &[asset_type as u8]],
+        bump = price_history.load()?.bump
+    )]
+    pub price_history: AccountLoader<'info, PriceHistory>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeMarketPrice<'info> {
+    #[account(
+        mut,
+        seeds = [b"market"],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        seeds = [b"oracle_config"],
+        bump = oracle_config.bump
+    )]
+    pub oracle_config: Account<'info, OracleConfig>,
+    
+    /// CHECK: Price account for oracle_config.mode - validated in oracle::load_price
+    pub price_account: AccountInfo<'info>,
+    
+    #[account(
+        seeds = [b"price_accumulator", market.asset_symbol.as_bytes(), &[market.asset_type as u8]],
+        bump = price_accumulator.bump
+    )]
+    pub price_accumulator: Account<'info, PriceAccumulator>,
+    
+    /// CHECK: Trading schedule PDA - read with calendar::load_schedule
+    #[account(
+        seeds = [b"trading_schedule", market.asset_symbol.as_bytes(), &[market.asset_type as u8]],
+        bump
+    )]
+    pub trading_schedule: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitHeatmap<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + std::mem::size_of::<SpreadHeatmap>(),
+        seeds = [b"heatmap", market.asset_symbol.as_bytes(), &[market.asset_type as u8]],
+        bump
+    )]
+    pub heatmap: AccountLoader<'info, SpreadHeatmap>,
+    
+    #[account(
+        seeds = [b"market"],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        seeds = [b"oracle_config"],
+        bump = oracle_config.bump,
+        constraint = oracle_config.admin == admin.key() @ ErrorCode::Unauthorized
+    )]
+    pub oracle_config: Account<'info, OracleConfig>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CrankHeatmap<'info> {
+    #[account(
+        mut,
+        seeds = [b"heatmap", market.asset_symbol.as_bytes(), &[market.asset_type as u8]],
+        bump = heatmap.load()?.bump
+    )]
+    pub heatmap: AccountLoader<'info, SpreadHeatmap>,
+    
+    #[account(
+        seeds = [b"market"],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        seeds = [b"oracle_config"],
+        bump = oracle_config.bump
+    )]
+    pub oracle_config: Account<'info, OracleConfig>,
+    
+    /// CHECK: Price account for oracle_config.mode - validated in oracle::load_price
+    pub price_account: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitTenorHeatmap<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + std::mem::size_of::<TenorHeatmap>(),
+        seeds = [b"tenor_heatmap"],
+        bump
+    )]
+    pub tenor_heatmap: AccountLoader<'info, TenorHeatmap>,
+    
+    #[account(
+        seeds = [b"oracle_config"],
+        bump = oracle_config.bump,
+        constraint = oracle_config.admin == admin.key() @ ErrorCode::Unauthorized
+    )]
+    pub oracle_config: Account<'info, OracleConfig>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CrankTenorHeatmap<'info> {
+    #[account(
+        mut,
+        seeds = [b"tenor_heatmap"],
+        bump = tenor_heatmap.load()?.bump
+    )]
+    pub tenor_heatmap: AccountLoader<'info, TenorHeatmap>,
+}
+
+#[derive(Accounts)]
+pub struct InitAmmPool<'info> {
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + 32 + 32 + 32 + 32 + 32 + 32 + 8 + 8 + 8 + 2 + 2 + 8 + 8 + 1,
+        seeds = [b"amm_pool"],
+        bump
+    )]
+    pub pool: Account<'info, AmmPool>,
+    
+    pub long_mint: Account<'info, Mint>,
+    pub short_mint: Account<'info, Mint>,
+    
+    #[account(
+        init_if_needed,
+        payer = admin,
+        mint::decimals = 6,
+        mint::authority = pool,
+        seeds = [b"lp_mint"],
+        bump
+    )]
+    pub lp_mint: Account<'info, Mint>,
+    
+    #[account(
+        init_if_needed,
+        payer = admin,
+        token::mint = long_mint,
+        token::authority = pool,
+        seeds = [b"long_vault"],
+        bump
+    )]
+    pub long_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = admin,
+        token::mint = short_mint,
+        token::authority = pool,
+        seeds = [b"short_vault"],
+        bump
+    )]
+    pub short_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,  // Changed from Token2022 to Token
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct AddLiquidity<'info> {
+    #[account(
+        mut,
+        seeds = [b"amm_pool"],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, AmmPool>,
+    
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + 32 + 8 + 8 + 8 + 1,
+        seeds = [b"lp_position", user.key().as_ref()],
+        bump
+    )]
+    pub lp_position: Account<'info, LpPosition>,
+    
+    #[account(mut)]
+    pub long_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub short_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub lp_mint: Account<'info, Mint>,
+    
+    #[account(mut)]
+    pub user_long_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub user_short_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub user_lp_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,  // Changed from Token2022 to Token
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SwapCfdTokens<'info> {
+    #[account(
+        mut,
+        seeds = [b"amm_pool"],
+        bump = pool.bump
+    )]
+    pub pool: Account<'info, AmmPool>,
+    
+    #[account(mut)]
+    pub vault_in: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub vault_out: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub user_token_in: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub user_token_out: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,  // Changed from Token2022 to Token
+}
+
+#[derive(Accounts)]
+pub struct InitGovernance<'info> {
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + 32 + 32 + 32 + 8 + 8 + 32 + 32 + 8 + 8 + 2 + 8 + 8 + 8 + 8 + 2 + 1,
+        seeds = [b"governance"],
+        bump
+    )]
+    pub governance: Account<'info, CadenGovernance>,
+    
+    pub caden_mint: Account<'info, Mint>,
+    
+    #[account(
+        init_if_needed,
+        payer = admin,
+        mint::decimals = 6,
+        mint::authority = governance,
+        seeds = [b"staked_caden_mint"],
+        bump
+    )]
+    pub staked_caden_mint: Account<'info, Mint>,
+    
+    #[account(
+        init_if_needed,
+        payer = admin,
+        token::mint = usdc_mint,
+        token::authority = governance,
+        seeds = [b"protocol_fee_vault"],
+        bump
+    )]
+    pub protocol_fee_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = admin,
+        token::mint = usdc_mint,
+        token::authority = governance,
+        seeds = [b"buyback_vault"],
+        bump
+    )]
+    pub buyback_vault: Account<'info, TokenAccount>,
+    
+    pub usdc_mint: Account<'info, Mint>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,  // Changed from Token2022 to Token
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct StakeCaden<'info> {
+    #[account(
+        mut,
+        seeds = [b"governance"],
+        bump = governance.bump
+    )]
+    pub governance: Account<'info, CadenGovernance>,
+    
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + 32 + 8 + 8 + 8 + 8 + 8 + 1,
+        seeds = [b"staking_position", user.key().as_ref()],
+        bump
+    )]
+    pub staking_position: Account<'info, StakingPosition>,
+    
+    #[account(mut)]
+    pub caden_mint: Account<'info, Mint>,
+    
+    #[account(mut)]
+    pub staked_caden_mint: Account<'info, Mint>,
+    
+    #[account(mut)]
+    pub governance_caden_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub user_caden_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub user_stk_caden_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    pub clock: Sysvar<'info, Clock>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimFees<'info> {
+    #[account(
+        mut,
+        seeds = [b"governance"],
+        bump = governance.bump
+    )]
+    pub governance: Account<'info, CadenGovernance>,
+    
+    #[account(
+        mut,
+        seeds = [b"staking_position", user.key().as_ref()],
+        bump = staking_position.bump
+    )]
+    pub staking_position: Account<'info, StakingPosition>,
+    
+    #[account(mut)]
+    pub protocol_fee_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub user_usdc_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    pub clock: Sysvar<'info, Clock>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct BuybackCaden<'info> {
+    #[account(
+        mut,
+        seeds = [b"governance"],
+        bump = governance.bump
+    )]
+    pub governance: Account<'info, CadenGovernance>,
+    
+    #[account(mut)]
+    pub protocol_fee_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub buyback_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(asset_symbol: String, asset_type: AssetType)]
+pub struct UpdateOracle<'info> {
+    #[account(mut)]
+    pub oracle_mock: Account<'info, OracleMock>,
+    
+    #[account(
+        seeds = [b"oracle_config"],
+        bump = oracle_config.bump
+    )]
+    pub oracle_config: Account<'info, OracleConfig>,
+    
+    #[account(
+        seeds = [b"oracle_updater", oracle_mock.key().as_ref(), updater.key().as_ref()],
+        bump = updater_role.bump
+    )]
+    pub updater_role: Option<Account<'info, OracleUpdater>>,
+    
+    #[account(
+        init_if_needed,
+        payer = updater,
+        space = PriceAccumulator::SPACE,
+        seeds = [b"price_accumulator", asset_symbol.as_bytes(), &[asset_type as u8]],
+        bump
+    )]
+    pub price_accumulator: Account<'info, PriceAccumulator>,
+    
+    #[account(
+        mut,
+        seeds = [b"price_history", asset_symbol.as_bytes(), &[asset_type as u8]],
+        bump = price_history.load()?.bump
+    )]
+    pub price_history: AccountLoader<'info, PriceHistory>,
+    
+    #[account(mut)]
+    pub updater: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(position_side: PositionSide, size: u64, leverage: u8)]
+pub struct MintCfd<'info> {
+    #[account(
+        init,
+        payer = user,
+        space = 8 + 32 + 1 + 8 + 8 + 8 + 1 + 8 + 1 + 8 + 1,
+        seeds = [b"position", user.key().as_ref()],
+        bump
+    )]
+    pub position: Account<'info, Position>,
+    
+    #[account(
+        mut,
+        seeds = [b"market"],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        mut,
+        seeds = [b"usdc_vault"],
+        bump
+    )]
+    pub market_usdc_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"mint"],
+        bump
+    )]
+    pub cfd_mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        associated_token::mint = cfd_mint,
+        associated_token::authority = user
+    )]
+    pub user_cfd_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub user_usdc_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(
+        seeds = [b"oracle_config"],
+        bump = oracle_config.bump
+    )]
+    pub oracle_config: Account<'info, OracleConfig>,
+    
+    /// CHECK: Price account for oracle_config.mode - validated in oracle::load_price
+    pub price_account: AccountInfo<'info>,
+    
+    /// CHECK: Trading schedule PDA - read with calendar::load_schedule
+    #[account(
+        seeds = [b"trading_schedule", market.asset_symbol.as_bytes(), &[market.asset_type as u8]],
+        bump
+    )]
+    pub trading_schedule: UncheckedAccount<'info>,
+    
+    pub token_program: Program<'info, Token>,  // Changed from Token2022 to Token
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitTokenMint<'info> {
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + 32 + 1 + 4 + 16, // discriminator + mint + decimals + string_len + up to 16 chars
+        seeds = [b"token_mint"],
+        bump
+    )]
+    pub token_mint: Account<'info, TokenMint>,
+    
+    #[account(
+        init_if_needed,
+        payer = user,
+        mint::decimals = 6,
+        mint::authority = user,
+        seeds = [b"mint"],
+        bump
+    )]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,  // Changed from Token2022 to Token
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct LiquidatePosition<'info> {
+    #[account(
+        mut,
+        seeds = [b"position", position.owner.as_ref()],
+        bump = position.bump,
+        constraint = !position.liquidated @ ErrorCode::PositionAlreadyLiquidated
+    )]
+    pub position: Account<'info, Position>,
+    
+    #[account(
+        mut,
+        seeds = [b"market"],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        mut,
+        seeds = [b"usdc_vault"],
+        bump
+    )]
+    pub market_usdc_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"mint"],
+        bump
+    )]
+    pub cfd_mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        associated_token::mint = cfd_mint,
+        associated_token::authority = position.owner
+    )]
+    pub user_cfd_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = position.owner
+    )]
+    pub user_usdc_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = liquidator
+    )]
+    pub liquidator_usdc_account: Account<'info, TokenAccount>,
+    
+    /// CHECK: USDC mint address
+    pub usdc_mint: AccountInfo<'info>,
+    
+    #[account(mut)]
+    pub liquidator: Signer<'info>,
+    
+    #[account(
+        seeds = [b"oracle_config"],
+        bump = oracle_config.bump
+    )]
+    pub oracle_config: Account<'info, OracleConfig>,
+    
+    /// CHECK: Price account for oracle_config.mode - validated in oracle::load_price
+    pub price_account: AccountInfo<'info>,
+    
+    #[account(
+        seeds = [b"price_accumulator", market.asset_symbol.as_bytes(), &[market.asset_type as u8]],
+        bump = price_accumulator.bump
+    )]
+    pub price_accumulator: Account<'info, PriceAccumulator>,
+    
+    pub token_program: Program<'info, Token>,  // Changed from Token2022 to Token
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SettleMarket<'info> {
+    #[account(
+        mut,
+        seeds = [b"market"],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+    
+    #[account(
+        mut,
+        seeds = [b"position", user.key().as_ref()],
+        bump = position.bump,
+        close = user
+    )]
+    pub position: Account<'info, Position>,
+    
+    #[account(
+        mut,
+        seeds = [b"usdc_vault"],
+        bump
+    )]
+    pub market_usdc_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [b"mint"],
+        bump
+    )]
+    pub cfd_mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        associated_token::mint = cfd_mint,
+        associated_token::authority = user
+    )]
+    pub user_cfd_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        associated_token::mint = usdc_mint,
+        associated_token::authority = user
+    )]
+    pub user_usdc_account: Account<'info, TokenAccount>,
+    
+    /// CHECK: USDC mint address
+    pub usdc_mint: AccountInfo<'info>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,  // Changed from Token2022 to Token
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(asset_symbol: String, asset_type: AssetType, settlement_time: u64)]
+pub struct MintSettlementSlotNft<'info> {
+    #[account(
+        mut,
+        seeds = [b"slot_pricing", asset_symbol.as_bytes(), &[asset_type as u8], &settlement_time.to_le_bytes()],
+        bump = slot_pricing.bump
+    )]
+    pub slot_pricing: Box<Account<'info, SlotPricing>>,
+    
+    #[account(
+        seeds = [b"governance"],
+        bump = governance.bump
+    )]
+    pub governance: Box<Account<'info, CadenGovernance>>,
+    
+    #[account(mut, address = governance.protocol_fee_vault)]
+    pub protocol_fee_vault: Box<Account<'info, TokenAccount>>,
+    
+    #[account(mut, token::mint = protocol_fee_vault.mint, token::authority = owner)]
+    pub owner_usdc_account: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + 8 + 1, // SlotCounter size
+        seeds = [b"slot_counter"],
+        bump
+    )]
+    pub slot_counter: Account<'info, SlotCounter>,
+    
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + 8 + 4 + 10 + 1 + 8 + 8 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 1 + 1 + 1, // SettlementSlot size
+        seeds = [b"settlement_slot".as_ref(), &slot_counter.next_slot_id.to_le_bytes()],
+        bump
+    )]
+    pub settlement_slot: Account<'info, SettlementSlot>,
+    
+    #[account(
+        init,
+        payer = owner,
+        seeds = [b"slot_mint", settlement_slot.key().as_ref()],
+        bump,
+        mint::decimals = 0,
+        mint::authority = settlement_slot,
+        mint::token_program = token_2022_program,
+        extensions::metadata_pointer::authority = settlement_slot,
+        extensions::metadata_pointer::metadata_address = slot_mint,
+        extensions::permanent_delegate::delegate = settlement_slot,
+        extensions::close_authority::authority = settlement_slot,
+    )]
+    pub slot_mint: InterfaceAccount<'info, token_interface::Mint>,
+    
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = slot_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub owner_token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+    
+    /// CHECK: Trading schedule PDA - read with calendar::load_schedule
+    #[account(
+        seeds = [b"trading_schedule", asset_symbol.as_bytes(), &[asset_type as u8]],
+        bump
+    )]
+    pub trading_schedule: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub clock: Sysvar<'info, Clock>,
+    pub token_program: Program<'info, Token>,
+    pub token_2022_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct TradeSettlementSlot<'info> {
+    #[account(
+        mut,
+        seeds = [b"settlement_slot".as_ref(), &settlement_slot.slot_id.to_le_bytes()],
+        bump = settlement_slot.bump
+    )]
+    pub settlement_slot: Box<Account<'info, SettlementSlot>>,
+    
+    #[account(address = settlement_slot.mint)]
+    pub slot_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+    
+    #[account(
+        mut,
+        token::mint = slot_mint,
+        token::authority = seller,
+        token::token_program = token_2022_program,
+        constraint = seller_token_account.amount == 1 @ ErrorCode::Unauthorized
+    )]
+    pub seller_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = slot_mint,
+        associated_token::authority = buyer,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub buyer_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    
+    #[account(
+        seeds = [b"governance"],
+        bump = governance.bump
+    )]
+    pub governance: Box<Account<'info, CadenGovernance>>,
+    
+    #[account(mut, address = governance.protocol_fee_vault)]
+    pub protocol_fee_vault: Box<Account<'info, TokenAccount>>,
+    
+    #[account(mut, token::mint = protocol_fee_vault.mint, token::authority = buyer)]
+    pub buyer_usdc_account: Box<Account<'info, TokenAccount>>,
+    
+    #[account(mut, token::mint = protocol_fee_vault.mint, token::authority = seller)]
+    pub seller_usdc_account: Box<Account<'info, TokenAccount>>,
+    
+    pub seller: Signer<'info>,
+    
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub token_2022_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ListSettlementSlot<'info> {
+    #[account(
+        seeds = [b"settlement_slot".as_ref(), &settlement_slot.slot_id.to_le_bytes()],
+        bump = settlement_slot.bump
+    )]
+    pub settlement_slot: Box<Account<'info, SettlementSlot>>,
+    
+    #[account(
+        init,
+        payer = seller,
+        space = 8 + 32 + 32 + 8 + 8 + 1, // SlotListing size
+        seeds = [b"slot_listing", settlement_slot.key().as_ref()],
+        bump
+    )]
+    pub listing: Box<Account<'info, SlotListing>>,
+    
+    #[account(address = settlement_slot.mint)]
+    pub slot_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+    
+    #[account(
+        mut,
+        token::mint = slot_mint,
+        token::authority = seller,
+        token::token_program = token_2022_program,
+        constraint = seller_token_account.amount == 1 @ ErrorCode::Unauthorized
+    )]
+    pub seller_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    
+    #[account(
+        init,
+        payer = seller,
+        associated_token::mint = slot_mint,
+        associated_token::authority = listing,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub escrow_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    
+    #[account(mut)]
+    pub seller: Signer<'info>,
+    
+    pub token_2022_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct BuySettlementSlot<'info> {
+    #[account(
+        mut,
+        seeds = [b"settlement_slot".as_ref(), &settlement_slot.slot_id.to_le_bytes()],
+        bump = settlement_slot.bump
+    )]
+    pub settlement_slot: Box<Account<'info, SettlementSlot>>,
+    
+    #[account(
+        mut,
+        close = seller,
+        has_one = seller,
+        seeds = [b"slot_listing", settlement_slot.key().as_ref()],
+        bump = listing.bump
+    )]
+    pub listing: Box<Account<'info, SlotListing>>,
+    
+    #[account(address = settlement_slot.mint)]
+    pub slot_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+    
+    #[account(
+        mut,
+        associated_token::mint = slot_mint,
+        associated_token::authority = listing,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub escrow_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = slot_mint,
+        associated_token::authority = buyer,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub buyer_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    
+    #[account(
+        seeds = [b"governance"],
+        bump = governance.bump
+    )]
+    pub governance: Box<Account<'info, CadenGovernance>>,
+    
+    #[account(mut, address = governance.protocol_fee_vault)]
+    pub protocol_fee_vault: Box<Account<'info, TokenAccount>>,
+    
+    #[account(mut, token::mint = protocol_fee_vault.mint, token::authority = buyer)]
+    pub buyer_usdc_account: Box<Account<'info, TokenAccount>>,
+    
+    #[account(mut, token::mint = protocol_fee_vault.mint, token::authority = seller)]
+    pub seller_usdc_account: Box<Account<'info, TokenAccount>>,
+    
+    /// CHECK: Listing seller, receives the listing and escrow rent - checked by has_one
+    #[account(mut)]
+    pub seller: AccountInfo<'info>,
+    
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub token_2022_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelSlotListing<'info> {
+    #[account(
+        mut,
+        close = seller,
+        has_one = seller,
+        seeds = [b"slot_listing", listing.settlement_slot.as_ref()],
+        bump = listing.bump
+    )]
+    pub listing: Box<Account<'info, SlotListing>>,
+    
+    #[account(
+        mut,
+        associated_token::mint = slot_mint,
+        associated_token::authority = listing,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub escrow_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    
+    #[account(address = settlement_slot.mint)]
+    pub slot_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+    
+    #[account(
+        seeds = [b"settlement_slot".as_ref(), &settlement_slot.slot_id.to_le_bytes()],
+        bump = settlement_slot.bump,
+        address = listing.settlement_slot
+    )]
+    pub settlement_slot: Box<Account<'info, SettlementSlot>>,
+    
+    #[account(
+        mut,
+        token::mint = slot_mint,
+        token::authority = seller,
+        token::token_program = token_2022_program,
+    )]
+    pub seller_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    
+    #[account(mut)]
+    pub seller: Signer<'info>,
+    
+    pub token_2022_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+#[instruction(asset_symbol: String, asset_type: AssetType, settlement_time: u64)]
+pub struct SetSlotPricing<'info> {
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + 4 + 10 + 1 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 1, // SlotPricing size
+        seeds = [b"slot_pricing", asset_symbol.as_bytes(), &[asset_type as u8], &settlement_time.to_le_bytes()],
+        bump
+    )]
+    pub slot_pricing: Account<'info, SlotPricing>,
+    
+    #[account(
+        seeds = [b"governance"],
+        bump = governance.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub governance: Account<'info, CadenGovernance>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(asset_symbol: String, asset_type: AssetType, epoch: u64)]
+pub struct CreateSlotAuction<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + 4 + 10 + 1 + 8 * 12 + 1 + 1, // SlotAuction size
+        seeds = [b"slot_auction", asset_symbol.as_bytes(), &[asset_type as u8], &epoch.to_le_bytes()],
+        bump
+    )]
+    pub auction: Account<'info, SlotAuction>,
+    
+    #[account(
+        mut,
+        seeds = [b"slot_pricing", asset_symbol.as_bytes(), &[asset_type as u8], &0u64.to_le_bytes()],
+        bump = slot_pricing.bump
+    )]
+    pub slot_pricing: Account<'info, SlotPricing>,
+    
+    #[account(
+        seeds = [b"governance"],
+        bump = governance.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub governance: Account<'info, CadenGovernance>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct BuyAuctionSlot<'info> {
+    #[account(
+        mut,
+        seeds = [b"slot_auction", auction.asset_symbol.as_bytes(), &[auction.asset_type as u8], &auction.epoch.to_le_bytes()],
+        bump = auction.bump
+    )]
+    pub auction: Box<Account<'info, SlotAuction>>,
+    
+    #[account(
+        mut,
+        seeds = [b"slot_pricing", auction.asset_symbol.as_bytes(), &[auction.asset_type as u8], &0u64.to_le_bytes()],
+        bump = slot_pricing.bump
+    )]
+    pub slot_pricing: Box<Account<'info, SlotPricing>>,
+    
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + 8 + 1, // SlotCounter size
+        seeds = [b"slot_counter"],
+        bump
+    )]
+    pub slot_counter: Box<Account<'info, SlotCounter>>,
+    
+    #[account(
+        init,
+        payer = buyer,
+        space = 8 + 8 + 4 + 10 + 1 + 8 + 8 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 1 + 1 + 1, // SettlementSlot size
+        seeds = [b"settlement_slot".as_ref(), &slot_counter.next_slot_id.to_le_bytes()],
+        bump
+    )]
+    pub settlement_slot: Box<Account<'info, SettlementSlot>>,
+    
+    #[account(
+        init,
+        payer = buyer,
+        seeds = [b"slot_mint", settlement_slot.key().as_ref()],
+        bump,
+        mint::decimals = 0,
+        mint::authority = settlement_slot,
+        mint::token_program = token_2022_program,
+        extensions::metadata_pointer::authority = settlement_slot,
+        extensions::metadata_pointer::metadata_address = slot_mint,
+        extensions::permanent_delegate::delegate = settlement_slot,
+        extensions::close_authority::authority = settlement_slot,
+    )]
+    pub slot_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+    
+    #[account(
+        init,
+        payer = buyer,
+        associated_token::mint = slot_mint,
+        associated_token::authority = buyer,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub buyer_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    
+    /// CHECK: Trading schedule PDA - read with calendar::load_schedule
+    #[account(
+        seeds = [b"trading_schedule", auction.asset_symbol.as_bytes(), &[auction.asset_type as u8]],
+        bump
+    )]
+    pub trading_schedule: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [b"governance"],
+        bump = governance.bump
+    )]
+    pub governance: Box<Account<'info, CadenGovernance>>,
+    
+    #[account(mut, address = governance.protocol_fee_vault)]
+    pub protocol_fee_vault: Box<Account<'info, TokenAccount>>,
+    
+    #[account(mut, token::mint = protocol_fee_vault.mint, token::authority = buyer)]
+    pub buyer_usdc_account: Box<Account<'info, TokenAccount>>,
+    
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub token_2022_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeSlotAuction<'info> {
+    #[account(
+        mut,
+        seeds = [b"slot_auction", auction.asset_symbol.as_bytes(), &[auction.asset_type as u8], &auction.epoch.to_le_bytes()],
+        bump = auction.bump
+    )]
+    pub auction: Account<'info, SlotAuction>,
+    
+    #[account(
+        mut,
+        seeds = [b"slot_pricing", auction.asset_symbol.as_bytes(), &[auction.asset_type as u8], &0u64.to_le_bytes()],
+        bump = slot_pricing.bump
+    )]
+    pub slot_pricing: Account<'info, SlotPricing>,
+}
+
+#[derive(Accounts)]
+pub struct ListSlotForRent<'info> {
+    #[account(
+        seeds = [b"settlement_slot".as_ref(), &settlement_slot.slot_id.to_le_bytes()],
+        bump = settlement_slot.bump
+    )]
+    pub settlement_slot: Box<Account<'info, SettlementSlot>>,
+    
+    #[account(
+        init,
+        payer = lender,
+        space = 8 + 32 + 32 + 32 + 8 + 8 + 32 + 8 + 8 + 1, // SlotRental size
+        seeds = [b"slot_rental", settlement_slot.key().as_ref()],
+        bump
+    )]
+    pub rental: Box<Account<'info, SlotRental>>,
+    
+    #[account(address = settlement_slot.mint)]
+    pub slot_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+    
+    #[account(
+        mut,
+        token::mint = slot_mint,
+        token::authority = lender,
+        token::token_program = token_2022_program,
+        constraint = lender_token_account.amount == 1 @ ErrorCode::Unauthorized
+    )]
+    pub lender_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    
+    #[account(
+        init,
+        payer = lender,
+        associated_token::mint = slot_mint,
+        associated_token::authority = rental,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub escrow_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    
     #[account(
-        mut,
         seeds = [b"governance"],
         bump = governance.bump
     )]
-    pub governance: Account<'info, CadenGovernance>,
+    pub governance: Box<Account<'info, CadenGovernance>>,
+    
+    // Rental fees are paid in the protocol's USDC
+    #[account(address = governance.protocol_fee_vault)]
+    pub protocol_fee_vault: Box<Account<'info, TokenAccount>>,
+    
+    #[account(token::mint = protocol_fee_vault.mint, token::authority = lender)]
+    pub lender_usdc_account: Box<Account<'info, TokenAccount>>,
+    
+    #[account(mut)]
+    pub lender: Signer<'info>,
     
+    pub token_2022_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RentSettlementSlot<'info> {
     #[account(
         mut,
-        seeds = [b"staking_position", user.key().as_ref()],
-        bump = staking_position.bump
+        seeds = [b"slot_rental", settlement_slot.key().as_ref()],
+        bump = rental.bump
     )]
-    pub staking_position: Account<'info, StakingPosition>,
+    pub rental: Account<'info, SlotRental>,
+    
+    #[account(
+        seeds = [b"settlement_slot".as_ref(), &settlement_slot.slot_id.to_le_bytes()],
+        bump = settlement_slot.bump
+    )]
+    pub settlement_slot: Account<'info, SettlementSlot>,
+    
+    #[account(mut, address = rental.payment_account)]
+    pub lender_usdc_account: Account<'info, TokenAccount>,
+    
+    #[account(mut, token::mint = lender_usdc_account.mint, token::authority = renter)]
+    pub renter_usdc_account: Account<'info, TokenAccount>,
+    
+    pub renter: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct EndSlotRental<'info> {
+    #[account(
+        mut,
+        close = lender,
+        has_one = lender,
+        seeds = [b"slot_rental", rental.settlement_slot.as_ref()],
+        bump = rental.bump
+    )]
+    pub rental: Box<Account<'info, SlotRental>>,
+    
+    #[account(
+        mut,
+        associated_token::mint = slot_mint,
+        associated_token::authority = rental,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub escrow_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    
+    #[account(address = settlement_slot.mint)]
+    pub slot_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+    
+    #[account(
+        seeds = [b"settlement_slot".as_ref(), &settlement_slot.slot_id.to_le_bytes()],
+        bump = settlement_slot.bump,
+        address = rental.settlement_slot
+    )]
+    pub settlement_slot: Box<Account<'info, SettlementSlot>>,
+    
+    #[account(
+        mut,
+        token::mint = slot_mint,
+        token::authority = lender,
+        token::token_program = token_2022_program,
+    )]
+    pub lender_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
     
     #[account(mut)]
-    pub protocol_fee_vault: Account<'info, TokenAccount>,
+    pub lender: Signer<'info>,
+    
+    pub token_2022_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct FractionalizeSlot<'info> {
+    #[account(
+        seeds = [b"settlement_slot".as_ref(), &settlement_slot.slot_id.to_le_bytes()],
+        bump = settlement_slot.bump
+    )]
+    pub settlement_slot: Box<Account<'info, SettlementSlot>>,
+    
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + 32 + 32 + 32 + 8 + 8 + 8 + 1, // FractionVault size
+        seeds = [b"fraction_vault", settlement_slot.key().as_ref()],
+        bump
+    )]
+    pub fraction_vault: Box<Account<'info, FractionVault>>,
+    
+    // Reused if the slot was fractionalized and redeemed before; SPL mints cannot be closed
+    #[account(
+        init_if_needed,
+        payer = owner,
+        mint::decimals = 0,
+        mint::authority = fraction_vault,
+        seeds = [b"fraction_mint", settlement_slot.key().as_ref()],
+        bump,
+        constraint = fraction_mint.supply == 0 @ ErrorCode::FractionsOutstanding
+    )]
+    pub fraction_mint: Box<Account<'info, Mint>>,
+    
+    #[account(address = settlement_slot.mint)]
+    pub slot_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+    
+    #[account(
+        mut,
+        token::mint = slot_mint,
+        token::authority = owner,
+        token::token_program = token_2022_program,
+        constraint = owner_token_account.amount == 1 @ ErrorCode::Unauthorized
+    )]
+    pub owner_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = slot_mint,
+        associated_token::authority = fraction_vault,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub escrow_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = fraction_mint,
+        associated_token::authority = owner,
+    )]
+    pub owner_fraction_account: Box<Account<'info, TokenAccount>>,
     
     #[account(mut)]
-    pub user_usdc_account: Account<'info, TokenAccount>,
+    pub owner: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub token_2022_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RedeemSlot<'info> {
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"fraction_vault", fraction_vault.settlement_slot.as_ref()],
+        bump = fraction_vault.bump
+    )]
+    pub fraction_vault: Box<Account<'info, FractionVault>>,
+    
+    #[account(
+        seeds = [b"settlement_slot".as_ref(), &settlement_slot.slot_id.to_le_bytes()],
+        bump = settlement_slot.bump,
+        address = fraction_vault.settlement_slot
+    )]
+    pub settlement_slot: Box<Account<'info, SettlementSlot>>,
+    
+    #[account(mut, address = fraction_vault.fraction_mint)]
+    pub fraction_mint: Box<Account<'info, Mint>>,
+    
+    #[account(
+        mut,
+        token::mint = fraction_mint,
+        token::authority = redeemer,
+        constraint = redeemer_fraction_account.amount == fraction_mint.supply @ ErrorCode::FractionsOutstanding
+    )]
+    pub redeemer_fraction_account: Box<Account<'info, TokenAccount>>,
+    
+    /// CHECK: Vault owner, refunded the vault's rent - checked against fraction_vault.owner
+    #[account(mut, address = fraction_vault.owner)]
+    pub owner: AccountInfo<'info>,
+    
+    #[account(address = settlement_slot.mint)]
+    pub slot_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+    
+    #[account(
+        mut,
+        associated_token::mint = slot_mint,
+        associated_token::authority = fraction_vault,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub escrow_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    
+    #[account(
+        init_if_needed,
+        payer = redeemer,
+        associated_token::mint = slot_mint,
+        associated_token::authority = redeemer,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub redeemer_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
     
     #[account(mut)]
-    pub user: Signer<'info>,
+    pub redeemer: Signer<'info>,
     
-    pub clock: Sysvar<'info, Clock>,
     pub token_program: Program<'info, Token>,
+    pub token_2022_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct BuybackCaden<'info> {
+pub struct UpgradeSettlementSlot<'info> {
+    #[account(
+        mut,
+        seeds = [b"settlement_slot".as_ref(), &settlement_slot.slot_id.to_le_bytes()],
+        bump = settlement_slot.bump
+    )]
+    pub settlement_slot: Box<Account<'info, SettlementSlot>>,
+    
+    #[account(
+        mut,
+        seeds = [b"slot_pricing", settlement_slot.asset_symbol.as_bytes(), &[settlement_slot.asset_type as u8], &settlement_slot.settlement_time.to_le_bytes()],
+        bump = tn_pricing.bump
+    )]
+    pub tn_pricing: Box<Account<'info, SlotPricing>>,
+    
     #[account(
         mut,
+        seeds = [b"slot_pricing", settlement_slot.asset_symbol.as_bytes(), &[settlement_slot.asset_type as u8], &0u64.to_le_bytes()],
+        bump = t0_pricing.bump
+    )]
+    pub t0_pricing: Box<Account<'info, SlotPricing>>,
+    
+    #[account(mut, address = settlement_slot.mint)]
+    pub slot_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+    
+    #[account(
+        token::mint = slot_mint,
+        token::authority = owner,
+        token::token_program = token_2022_program,
+        constraint = owner_token_account.amount == 1 @ ErrorCode::Unauthorized
+    )]
+    pub owner_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    
+    #[account(
         seeds = [b"governance"],
         bump = governance.bump
     )]
-    pub governance: Account<'info, CadenGovernance>,
+    pub governance: Box<Account<'info, CadenGovernance>>,
+    
+    #[account(mut, address = governance.protocol_fee_vault)]
+    pub protocol_fee_vault: Box<Account<'info, TokenAccount>>,
+    
+    #[account(mut, token::mint = protocol_fee_vault.mint, token::authority = owner)]
+    pub owner_usdc_account: Box<Account<'info, TokenAccount>>,
+    
+    pub owner: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub token_2022_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct ExpireSettlementSlot<'info> {
+    #[account(
+        mut,
+        close = rent_recipient,
+        seeds = [b"settlement_slot".as_ref(), &settlement_slot.slot_id.to_le_bytes()],
+        bump = settlement_slot.bump
+    )]
+    pub settlement_slot: Box<Account<'info, SettlementSlot>>,
+    
+    #[account(
+        mut,
+        seeds = [b"slot_pricing", settlement_slot.asset_symbol.as_bytes(), &[settlement_slot.asset_type as u8], &settlement_slot.settlement_time.to_le_bytes()],
+        bump = slot_pricing.bump
+    )]
+    pub slot_pricing: Box<Account<'info, SlotPricing>>,
+    
+    #[account(mut, address = settlement_slot.mint)]
+    pub slot_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
+    
+    #[account(
+        mut,
+        token::mint = slot_mint,
+        token::authority = holder,
+        token::token_program = token_2022_program,
+        constraint = holder_token_account.amount == 1 @ ErrorCode::InvalidSettlementSlot
+    )]
+    pub holder_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
     
+    /// CHECK: Current slot holder, either a wallet or a listing, rental or fraction vault escrow -
+    /// checked by holder_token_account and slot_escrow_holder
     #[account(mut)]
-    pub protocol_fee_vault: Account<'info, TokenAccount>,
+    pub holder: AccountInfo<'info>,
     
+    /// CHECK: Receives the refunded rent: the holder, or the seller, lender or owner behind an
+    /// escrow holder - checked in the handler
     #[account(mut)]
-    pub buyback_vault: Account<'info, TokenAccount>,
+    pub rent_recipient: AccountInfo<'info>,
     
     #[account(mut)]
-    pub admin: Signer<'info>,
+    pub cranker: Signer<'info>,
     
-    pub token_program: Program<'info, Token>,
+    pub token_2022_program: Program<'info, Token2022>,
 }
 
 #[derive(Accounts)]
-pub struct UpdateOracle<'info> {
+pub struct SetSlotRoyalty<'info> {
     #[account(
         mut,
-        constraint = oracle_mock.admin == admin.key() @ ErrorCode::Unauthorized
+        seeds = [b"governance"],
+        bump = governance.bump,
+        has_one = admin @ ErrorCode::Unauthorized
     )]
-    pub oracle_mock: Account<'info, OracleMock>,
+    pub governance: Account<'info, CadenGovernance>,
     
-    #[account(mut)]
     pub admin: Signer<'info>,
 }
 
 #[derive(Accounts)]
-#[instruction(position_side: PositionSide, size: u64, leverage: u8)]
-pub struct MintCfd<'info> {
+#[instruction(asset_symbol: String, asset_type: AssetType, bet_amount: u64, is_long: bool, bet_seed: u64)]
+pub struct PlaceBet<'info> {
     #[account(
         init,
         payer = user,
-        space = 8 + 32 + 1 + 8 + 8 + 8 + 1 + 8 + 1 + 8 + 1,
-        seeds = [b"position", user.key().as_ref()],
+        space = 8 + 32 + 8 + 10 + 1 + 8 + 1 + 8 + 8 + 1 + 8 + 1, // Bet size
+        seeds = [b"bet", user.key().as_ref(), &bet_seed.to_le_bytes()],
         bump
     )]
-    pub position: Account<'info, Position>,
+    pub bet: Account<'info, Bet>,
+    
+    #[account(
+        seeds = [b"oracle_config"],
+        bump = oracle_config.bump
+    )]
+    pub oracle_config: Account<'info, OracleConfig>,
+    
+    /// CHECK: Price account for oracle_config.mode - validated in oracle::load_price
+    pub price_account: AccountInfo<'info>,
+    
+    /// CHECK: Trading schedule PDA - read with calendar::load_schedule
+    #[account(
+        seeds = [b"trading_schedule", asset_symbol.as_bytes(), &[asset_type as u8]],
+        bump
+    )]
+    pub trading_schedule: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(bet_id: u64, settlement_slot_id: u64)]
+pub struct InstantT0Settlement<'info> {
+    #[account(
+        mut,
+        seeds = [b"bet", bet.owner.as_ref(), &bet_id.to_le_bytes()],
+        bump = bet.bump
+    )]
+    pub bet: Account<'info, Bet>,
     
     #[account(
         mut,
-        seeds = [b"market"],
-        bump = market.bump
+        seeds = [b"settlement_slot".as_ref(), &settlement_slot_id.to_le_bytes()],
+        bump = settlement_slot.bump
     )]
-    pub market: Account<'info, Market>,
+    pub settlement_slot: Account<'info, SettlementSlot>,
+    
+    #[account(
+        token::mint = settlement_slot.mint,
+        constraint = slot_token_account.amount == 1 @ ErrorCode::Unauthorized
+    )]
+    pub slot_token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+    
+    #[account(
+        seeds = [b"slot_rental", settlement_slot.key().as_ref()],
+        bump = slot_rental.bump
+    )]
+    pub slot_rental: Option<Account<'info, SlotRental>>,
     
     #[account(
         mut,
-        seeds = [b"usdc_vault"],
+        seeds = [b"fraction_vault", settlement_slot.key().as_ref()],
+        bump = fraction_vault.bump
+    )]
+    pub fraction_vault: Option<Account<'info, FractionVault>>,
+    
+    #[account(mut)]
+    pub fraction_mint: Option<Account<'info, Mint>>,
+    
+    #[account(mut)]
+    pub user_fraction_account: Option<Account<'info, TokenAccount>>,
+    
+    pub token_program: Option<Program<'info, Token>>,
+    
+    #[account(
+        seeds = [b"oracle_config"],
+        bump = oracle_config.bump
+    )]
+    pub oracle_config: Account<'info, OracleConfig>,
+    
+    /// CHECK: Price account for oracle_config.mode - validated in oracle::load_price
+    pub price_account: AccountInfo<'info>,
+    
+    /// CHECK: Trading schedule PDA - read with calendar::load_schedule
+    #[account(
+        seeds = [b"trading_schedule", bet.asset_symbol.as_bytes(), &[bet.asset_type as u8]],
         bump
     )]
-    pub market_usdc_vault: Account<'info, TokenAccount>,
+    pub trading_schedule: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitMultiAssetOracle<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = table_space::<MultiAssetOracle, AssetPrice>(DEFAULT_ORACLE_CAPACITY),
+        seeds = [b"multi_oracle"],
+        bump
+    )]
+    pub multi_oracle: AccountLoader<'info, MultiAssetOracle>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
     
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(asset_symbol: String, asset_type: AssetType)]
+pub struct UpdateAssetPrice<'info> {
     #[account(
         mut,
-        seeds = [b"mint"],
+        seeds = [b"multi_oracle"],
+        bump = multi_oracle.load()?.bump
+    )]
+    pub multi_oracle: AccountLoader<'info, MultiAssetOracle>,
+    
+    #[account(
+        seeds = [b"oracle_config"],
+        bump = oracle_config.bump
+    )]
+    pub oracle_config: Account<'info, OracleConfig>,
+    
+    #[account(
+        init_if_needed,
+        payer = updater,
+        space = PriceAccumulator::SPACE,
+        seeds = [b"price_accumulator", asset_symbol.as_bytes(), &[asset_type as u8]],
         bump
     )]
-    pub cfd_mint: Account<'info, Mint>,
+    pub price_accumulator: Account<'info, PriceAccumulator>,
     
     #[account(
         mut,
-        associated_token::mint = cfd_mint,
-        associated_token::authority = user
+        seeds = [b"price_history", asset_symbol.as_bytes(), &[asset_type as u8]],
+        bump = price_history.load()?.bump
     )]
-    pub user_cfd_account: Account<'info, TokenAccount>,
+    pub price_history: AccountLoader<'info, PriceHistory>,
     
-    #[account(mut)]
-    pub user_usdc_account: Account<'info, TokenAccount>,
+    #[account(
+        seeds = [b"oracle_updater", multi_oracle.key().as_ref(), updater.key().as_ref()],
+        bump = updater_role.bump
+    )]
+    pub updater_role: Option<Account<'info, OracleUpdater>>,
     
     #[account(mut)]
-    pub user: Signer<'info>,
-    
-    pub oracle_mock: Account<'info, OracleMock>,
+    pub updater: Signer<'info>,
     
-    pub token_program: Program<'info, Token>,  // Changed from Token2022 to Token
-    pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct InitTokenMint<'info> {
+#[instruction(asset_symbol: String, asset_type: AssetType, settlement_time: u64)]
+pub struct CreateTenorVault<'info> {
     #[account(
-        init_if_needed,
-        payer = user,
-        space = 8 + 32 + 1 + 4 + 16, // discriminator + mint + decimals + string_len + up to 16 chars
-        seeds = [b"token_mint"],
+        init,
+        payer = payer,
+        space = 8 + 4 + 10 + 1 + 8 + 32 + 8 + 1, // TenorVault size
+        seeds = [
+            b"tenor_vault",
+            asset_symbol.as_bytes(),
+            &[asset_type as u8],
+            &settlement_time.to_le_bytes()
+        ],
         bump
     )]
-    pub token_mint: Account<'info, TokenMint>,
+    pub tenor_vault: Box<Account<'info, TenorVault>>,
     
     #[account(
-        init_if_needed,
-        payer = user,
-        mint::decimals = 6,
-        mint::authority = user,
-        seeds = [b"mint"],
+        init,
+        payer = payer,
+        mint::decimals = 0,
+        mint::authority = tenor_vault,
+        seeds = [b"tenor_mint", tenor_vault.key().as_ref()],
         bump
     )]
-    pub mint: Account<'info, Mint>,
+    pub tenor_mint: Box<Account<'info, Mint>>,
     
     #[account(mut)]
-    pub user: Signer<'info>,
+    pub payer: Signer<'info>,
     
-    pub token_program: Program<'info, Token>,  // Changed from Token2022 to Token
+    pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
 }
 
 #[derive(Accounts)]
-pub struct LiquidatePosition<'info> {
+pub struct WrapSettlementSlot<'info> {
     #[account(
-        mut,
-        seeds = [b"position", position.owner.as_ref()],
-        bump = position.bump,
-        constraint = !position.liquidated @ ErrorCode::PositionAlreadyLiquidated
+        seeds = [b"settlement_slot".as_ref(), &settlement_slot.slot_id.to_le_bytes()],
+        bump = settlement_slot.bump
     )]
-    pub position: Account<'info, Position>,
+    pub settlement_slot: Box<Account<'info, SettlementSlot>>,
     
     #[account(
         mut,
-        seeds = [b"market"],
-        bump = market.bump
+        seeds = [
+            b"tenor_vault",
+            settlement_slot.asset_symbol.as_bytes(),
+            &[settlement_slot.asset_type as u8],
+            &settlement_slot.settlement_time.to_le_bytes()
+        ],
+        bump = tenor_vault.bump
     )]
-    pub market: Account<'info, Market>,
+    pub tenor_vault: Box<Account<'info, TenorVault>>,
     
-    #[account(
-        mut,
-        seeds = [b"usdc_vault"],
-        bump
-    )]
-    pub market_usdc_vault: Account<'info, TokenAccount>,
+    #[account(mut, address = tenor_vault.tenor_mint)]
+    pub tenor_mint: Box<Account<'info, Mint>>,
     
-    #[account(
-        mut,
-        seeds = [b"mint"],
-        bump
-    )]
-    pub cfd_mint: Account<'info, Mint>,
+    #[account(address = settlement_slot.mint)]
+    pub slot_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
     
     #[account(
         mut,
-        associated_token::mint = cfd_mint,
-        associated_token::authority = position.owner
+        token::mint = slot_mint,
+        token::authority = owner,
+        token::token_program = token_2022_program,
+        constraint = owner_token_account.amount == 1 @ ErrorCode::Unauthorized
     )]
-    pub user_cfd_account: Account<'info, TokenAccount>,
+    pub owner_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
     
     #[account(
-        mut,
-        associated_token::mint = usdc_mint,
-        associated_token::authority = position.owner
+        init,
+        payer = owner,
+        associated_token::mint = slot_mint,
+        associated_token::authority = tenor_vault,
+        associated_token::token_program = token_2022_program,
     )]
-    pub user_usdc_account: Account<'info, TokenAccount>,
+    pub escrow_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
     
     #[account(
-        mut,
-        associated_token::mint = usdc_mint,
-        associated_token::authority = liquidator
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = tenor_mint,
+        associated_token::authority = owner,
     )]
-    pub liquidator_usdc_account: Account<'info, TokenAccount>,
-    
-    /// CHECK: USDC mint address
-    pub usdc_mint: AccountInfo<'info>,
+    pub owner_tenor_account: Box<Account<'info, TokenAccount>>,
     
     #[account(mut)]
-    pub liquidator: Signer<'info>,
-    
-    pub oracle_mock: Account<'info, OracleMock>,
+    pub owner: Signer<'info>,
     
-    pub token_program: Program<'info, Token>,  // Changed from Token2022 to Token
+    pub token_program: Program<'info, Token>,
+    pub token_2022_program: Program<'info, Token2022>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct SettleMarket<'info> {
+pub struct UnwrapSettlementSlot<'info> {
     #[account(
-        mut,
-        seeds = [b"market"],
-        bump = market.bump
+        seeds = [b"settlement_slot".as_ref(), &settlement_slot.slot_id.to_le_bytes()],
+        bump = settlement_slot.bump
     )]
-    pub market: Account<'info, Market>,
+    pub settlement_slot: Box<Account<'info, SettlementSlot>>,
     
     #[account(
         mut,
-        seeds = [b"position", user.key().as_ref()],
-        bump = position.bump,
-        close = user
+        seeds = [
+            b"tenor_vault",
+            settlement_slot.asset_symbol.as_bytes(),
+            &[settlement_slot.asset_type as u8],
+            &settlement_slot.settlement_time.to_le_bytes()
+        ],
+        bump = tenor_vault.bump
     )]
-    pub position: Account<'info, Position>,
+    pub tenor_vault: Box<Account<'info, TenorVault>>,
     
-    #[account(
-        mut,
-        seeds = [b"usdc_vault"],
-        bump
-    )]
-    pub market_usdc_vault: Account<'info, TokenAccount>,
+    #[account(mut, address = tenor_vault.tenor_mint)]
+    pub tenor_mint: Box<Account<'info, Mint>>,
     
     #[account(
         mut,
-        seeds = [b"mint"],
-        bump
+        token::mint = tenor_mint,
+        token::authority = holder,
     )]
-    pub cfd_mint: Account<'info, Mint>,
+    pub holder_tenor_account: Box<Account<'info, TokenAccount>>,
+    
+    #[account(address = settlement_slot.mint)]
+    pub slot_mint: Box<InterfaceAccount<'info, token_interface::Mint>>,
     
     #[account(
         mut,
-        associated_token::mint = cfd_mint,
-        associated_token::authority = user
+        associated_token::mint = slot_mint,
+        associated_token::authority = tenor_vault,
+        associated_token::token_program = token_2022_program,
     )]
-    pub user_cfd_account: Account<'info, TokenAccount>,
+    pub escrow_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
     
     #[account(
-        mut,
-        associated_token::mint = usdc_mint,
-        associated_token::authority = user
+        init_if_needed,
+        payer = holder,
+        associated_token::mint = slot_mint,
+        associated_token::authority = holder,
+        associated_token::token_program = token_2022_program,
     )]
-    pub user_usdc_account: Account<'info, TokenAccount>,
-    
-    /// CHECK: USDC mint address
-    pub usdc_mint: AccountInfo<'info>,
+    pub holder_token_account: Box<InterfaceAccount<'info, token_interface::TokenAccount>>,
     
     #[account(mut)]
-    pub user: Signer<'info>,
-    
-    pub oracle_mock: Account<'info, OracleMock>,
+    pub holder: Signer<'info>,
     
-    pub token_program: Program<'info, Token>,  // Changed from Token2022 to Token
+    pub token_program: Program<'info, Token>,
+    pub token_2022_program: Program<'info, Token2022>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-#[instruction(asset_symbol: String, asset_type: AssetType, settlement_time: u64, slot_duration: u64, slot_price: u64, nft_seed: u64)]
-pub struct MintSettlementSlotNft<'info> {
+#[instruction(asset_symbol: String, asset_type: AssetType, settlement_time: u64)]
+pub struct CreateSettlementSlotPool<'info> {
     #[account(
         init,
-        payer = owner,
-        space = 8 + 8 + 10 + 1 + 8 + 8 + 32 + 8 + 8 + 8 + 1 + 1 + 1, // SettlementSlot size (added asset_type)
-        seeds = [b"settlement_slot", owner.key().as_ref(), &nft_seed.to_le_bytes()],
+        payer = creator,
+        space = 8 + 8 + 4 + 10 + 1 + 8 + 32 + 32 + 32 + 32 + 8 + 8 + 2 + 32 + 32 + 8 + 32 + 8 + 1 + 1, // SettlementSlotPool size
+        seeds = [
+            b"settlement_pool",
+            asset_symbol.as_bytes(),
+            &[asset_type as u8],
+            &settlement_time.to_le_bytes()
+        ],
         bump
     )]
-    pub settlement_slot: Account<'info, SettlementSlot>,
+    pub pool: Account<'info, SettlementSlotPool>,
     
-    #[account(mut)]
-    pub owner: Signer<'info>,
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = 8 + 4 + MAX_REGISTERED_POOLS * (4 + 10 + 1 + 8 + 32) + 1, // PoolRegistry size
+        seeds = [b"pool_registry"],
+        bump
+    )]
+    pub pool_registry: Account<'info, PoolRegistry>,
     
-    pub clock: Sysvar<'info, Clock>,
-    pub system_program: Program<'info, System>,
-}
-
-#[derive(Accounts)]
-pub struct TradeSettlementSlot<'info> {
     #[account(
-        mut,
-        seeds = [b"settlement_slot", settlement_slot.owner.as_ref(), &settlement_slot.created_slot.to_le_bytes()],
-        bump = settlement_slot.bump
+        seeds = [b"governance"],
+        bump = governance.bump,
+        constraint = governance.admin == creator.key() @ ErrorCode::Unauthorized
     )]
-    pub settlement_slot: Account<'info, SettlementSlot>,
+    pub governance: Box<Account<'info, CadenGovernance>>,
     
-    #[account(mut)]
-    pub seller: Signer<'info>,
+    #[account(address = governance.protocol_fee_vault)]
+    pub protocol_fee_vault: Box<Account<'info, TokenAccount>>,
+    
+    // Pools always quote in the protocol's USDC
+    #[account(address = protocol_fee_vault.mint)]
+    pub usdc_mint: Account<'info, Mint>,
+    
+    // Token B is the slot-backed tenor token for the pool's asset and T+N
+    #[account(
+        seeds = [
+            b"tenor_vault",
+            asset_symbol.as_bytes(),
+            &[asset_type as u8],
+            &settlement_time.to_le_bytes()
+        ],
+        bump = tenor_vault.bump
+    )]
+    pub tenor_vault: Box<Account<'info, TenorVault>>,
+    
+    #[account(address = tenor_vault.tenor_mint)]
+    pub tenor_mint: Account<'info, Mint>,
     
-    /// CHECK: Buyer public key
-    pub buyer: AccountInfo<'info>,
-}
-
-#[derive(Accounts)]
-#[instruction(asset_symbol: String, asset_type: AssetType, bet_amount: u64, is_long: bool, bet_seed: u64)]
-pub struct PlaceBet<'info> {
     #[account(
         init,
-        payer = user,
-        space = 8 + 32 + 8 + 10 + 1 + 8 + 1 + 8 + 8 + 1 + 8 + 1, // Bet size
-        seeds = [b"bet", user.key().as_ref(), &bet_seed.to_le_bytes()],
+        payer = creator,
+        token::mint = usdc_mint,
+        token::authority = pool_authority,
+        seeds = [b"token_a_vault", pool.key().as_ref()],
         bump
     )]
-    pub bet: Account<'info, Bet>,
+    pub token_a_vault: Account<'info, TokenAccount>,
     
-    pub multi_oracle: Account<'info, MultiAssetOracle>,
+    #[account(
+        init,
+        payer = creator,
+        token::mint = tenor_mint,
+        token::authority = pool_authority,
+        seeds = [b"token_b_vault", pool.key().as_ref()],
+        bump
+    )]
+    pub token_b_vault: Account<'info, TokenAccount>,
+    
+    #[account(
+        init,
+        payer = creator,
+        mint::decimals = 6,
+        mint::authority = pool_authority,
+        seeds = [b"pool_lp_mint", pool.key().as_ref()],
+        bump
+    )]
+    pub lp_mint: Account<'info, Mint>,
+    
+    #[account(
+        seeds = [b"pool_authority", pool.key().as_ref()],
+        bump
+    )]
+    pub pool_authority: SystemAccount<'info>,
     
     #[account(mut)]
-    pub user: Signer<'info>,
+    pub creator: Signer<'info>,
     
+    pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
 }
 
 #[derive(Accounts)]
-#[instruction(bet_id: u64, settlement_slot_id: u64)]
-pub struct InstantT0Settlement<'info> {
+pub struct AddLiquidityToPool<'info> {
     #[account(
         mut,
-        seeds = [b"bet", bet.owner.as_ref(), &bet_id.to_le_bytes()],
-        bump = bet.bump
+        seeds = [
+            b"settlement_pool",
+            pool.asset_symbol.as_bytes(),
+            &[pool.asset_type as u8],
+            &pool.settlement_time.to_le_bytes()
+        ],
+        bump = pool.bump
     )]
-    pub bet: Account<'info, Bet>,
+    pub pool: Account<'info, SettlementSlotPool>,
+    
+    #[account(mut, address = pool.token_a_vault)]
+    pub token_a_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, address = pool.token_b_vault)]
+    pub token_b_vault: Account<'info, TokenAccount>,
     
     #[account(
-        seeds = [b"settlement_slot", settlement_slot.owner.as_ref(), &settlement_slot_id.to_le_bytes()],
-        bump = settlement_slot.bump
+        mut,
+        token::mint = pool.token_a_mint,
+        token::authority = user
     )]
-    pub settlement_slot: Account<'info, SettlementSlot>,
-    
-    pub multi_oracle: Account<'info, MultiAssetOracle>,
+    pub user_token_a: Account<'info, TokenAccount>,
     
-    #[account(mut)]
-    pub user: Signer<'info>,
-}
-
-#[derive(Accounts)]
-pub struct InitMultiAssetOracle<'info> {
     #[account(
-        init,
-        payer = admin,
-        space = 8 + 32 + 4 + (100 * (10 + 1 + 8 + 8)) + 8 + 1, // MultiAssetOracle size
-        seeds = [b"multi_oracle"],
-        bump
+        mut,
+        token::mint = pool.token_b_mint,
+        token::authority = user
     )]
-    pub multi_oracle: Account<'info, MultiAssetOracle>,
+    pub user_token_b: Account<'info, TokenAccount>,
     
-    #[account(mut)]
-    pub admin: Signer<'info>,
+    #[account(mut, address = pool.lp_mint)]
+    pub lp_mint: Account<'info, Mint>,
     
-    pub system_program: Program<'info, System>,
-}
-
-#[derive(Accounts)]
-pub struct UpdateAssetPrice<'info> {
     #[account(
         mut,
-        seeds = [b"multi_oracle"],
-        bump = multi_oracle.bump
+        token::mint = lp_mint,
+        token::authority = user
     )]
-    pub multi_oracle: Account<'info, MultiAssetOracle>,
+    pub user_lp_account: Account<'info, TokenAccount>,
     
-    #[account(mut)]
-    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"pool_authority", pool.key().as_ref()],
+        bump
+    )]
+    pub pool_authority: SystemAccount<'info>,
+    
+    pub user: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
 }
 
 #[derive(Accounts)]
-pub struct CreateSettlementSlotPool<'info> {
+pub struct RemoveLiquidityFromPool<'info> {
     #[account(
-        init,
-        payer = creator,
-        space = 8 + 8 + 10 + 1 + 8 + 32 + 32 + 32 + 32 + 8 + 8 + 2 + 32 + 8 + 1 + 1, // SettlementSlotPool size
-        seeds = [b"settlement_pool", creator.key().as_ref()],
-        bump
+        mut,
+        seeds = [
+            b"settlement_pool",
+            pool.asset_symbol.as_bytes(),
+            &[pool.asset_type as u8],
+            &pool.settlement_time.to_le_bytes()
+        ],
+        bump = pool.bump
     )]
     pub pool: Account<'info, SettlementSlotPool>,
     
-    pub usdc_mint: Account<'info, Mint>,
-    pub slot_nft_mint: Account<'info, Mint>,
+    #[account(mut, address = pool.token_a_vault)]
+    pub token_a_vault: Account<'info, TokenAccount>,
+    
+    #[account(mut, address = pool.token_b_vault)]
+    pub token_b_vault: Account<'info, TokenAccount>,
     
     #[account(
-        init,
-        payer = creator,
-        token::mint = usdc_mint,
-        token::authority = pool_authority,
-        seeds = [b"token_a_vault", pool.key().as_ref()],
-        bump
+        mut,
+        token::mint = pool.token_a_mint,
+        token::authority = user
+    )]
+    pub user_token_a: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = pool.token_b_mint,
+        token::authority = user
     )]
-    pub token_a_vault: Account<'info, TokenAccount>,
+    pub user_token_b: Account<'info, TokenAccount>,
+    
+    #[account(mut, address = pool.lp_mint)]
+    pub lp_mint: Account<'info, Mint>,
     
     #[account(
-        init,
-        payer = creator,
-        token::mint = slot_nft_mint,
-        token::authority = pool_authority,
-        seeds = [b"token_b_vault", pool.key().as_ref()],
-        bump
+        mut,
+        token::mint = lp_mint,
+        token::authority = user
     )]
-    pub token_b_vault: Account<'info, TokenAccount>,
+    pub user_lp_account: Account<'info, TokenAccount>,
     
     #[account(
         seeds = [b"pool_authority", pool.key().as_ref()],
@@ -2502,33 +6396,43 @@ pub struct CreateSettlementSlotPool<'info> {
     )]
     pub pool_authority: SystemAccount<'info>,
     
-    #[account(mut)]
-    pub creator: Signer<'info>,
+    pub user: Signer<'info>,
     
     pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
 }
 
 #[derive(Accounts)]
-pub struct AddLiquidityToPool<'info> {
+pub struct SwapUsdcForSlots<'info> {
     #[account(
         mut,
-        seeds = [b"settlement_pool"],
+        seeds = [
+            b"settlement_pool",
+            pool.asset_symbol.as_bytes(),
+            &[pool.asset_type as u8],
+            &pool.settlement_time.to_le_bytes()
+        ],
         bump = pool.bump
     )]
     pub pool: Account<'info, SettlementSlotPool>,
     
-    #[account(mut)]
+    #[account(mut, address = pool.token_a_vault)]
     pub token_a_vault: Account<'info, TokenAccount>,
     
-    #[account(mut)]
+    #[account(mut, address = pool.token_b_vault)]
     pub token_b_vault: Account<'info, TokenAccount>,
     
-    #[account(mut)]
+    #[account(
+        mut,
+        token::mint = pool.token_a_mint,
+        token::authority = user
+    )]
     pub user_token_a: Account<'info, TokenAccount>,
     
-    #[account(mut)]
+    #[account(
+        mut,
+        token::mint = pool.token_b_mint,
+        token::authority = user
+    )]
     pub user_token_b: Account<'info, TokenAccount>,
     
     #[account(
@@ -2543,24 +6447,37 @@ pub struct AddLiquidityToPool<'info> {
 }
 
 #[derive(Accounts)]
-pub struct SwapUsdcForSlots<'info> {
+pub struct SwapSlotsForUsdc<'info> {
     #[account(
         mut,
-        seeds = [b"settlement_pool"],
+        seeds = [
+            b"settlement_pool",
+            pool.asset_symbol.as_bytes(),
+            &[pool.asset_type as u8],
+            &pool.settlement_time.to_le_bytes()
+        ],
         bump = pool.bump
     )]
     pub pool: Account<'info, SettlementSlotPool>,
     
-    #[account(mut)]
+    #[account(mut, address = pool.token_a_vault)]
     pub token_a_vault: Account<'info, TokenAccount>,
     
-    #[account(mut)]
+    #[account(mut, address = pool.token_b_vault)]
     pub token_b_vault: Account<'info, TokenAccount>,
     
-    #[account(mut)]
+    #[account(
+        mut,
+        token::mint = pool.token_a_mint,
+        token::authority = user
+    )]
     pub user_token_a: Account<'info, TokenAccount>,
     
-    #[account(mut)]
+    #[account(
+        mut,
+        token::mint = pool.token_b_mint,
+        token::authority = user
+    )]
     pub user_token_b: Account<'info, TokenAccount>,
     
     #[account(
@@ -2574,16 +6491,46 @@ pub struct SwapUsdcForSlots<'info> {
     pub token_program: Program<'info, Token>,
 }
 
+#[derive(Accounts)]
+pub struct GrowMultiAssetOracle<'info> {
+    #[account(
+        mut,
+        seeds = [b"multi_oracle"],
+        bump = multi_oracle.load()?.bump
+    )]
+    pub multi_oracle: AccountLoader<'info, MultiAssetOracle>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
 #[derive(Accounts)]
 pub struct InitOracleAggregator<'info> {
     #[account(
         init,
         payer = admin,
-        space = 8 + 32 + 4 + 4 + (100 * (10 + 1 + 8 + 8 + 8 + 8 + 8 + 1)) + 8 + 8 + 2 + 1, // OracleAggregator size
+        space = table_space::<OracleAggregator, PriceFeed>(DEFAULT_ORACLE_CAPACITY),
         seeds = [b"oracle_aggregator"],
         bump
     )]
-    pub oracle_aggregator: Account<'info, OracleAggregator>,
+    pub oracle_aggregator: AccountLoader<'info, OracleAggregator>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct GrowOracleAggregator<'info> {
+    #[account(
+        mut,
+        seeds = [b"oracle_aggregator"],
+        bump = oracle_aggregator.load()?.bump
+    )]
+    pub oracle_aggregator: AccountLoader<'info, OracleAggregator>,
     
     #[account(mut)]
     pub admin: Signer<'info>,
@@ -2592,46 +6539,218 @@ pub struct InitOracleAggregator<'info> {
 }
 
 #[derive(Accounts)]
+pub struct SetPriceFeedAccounts<'info> {
+    #[account(
+        mut,
+        seeds = [b"oracle_aggregator"],
+        bump = oracle_aggregator.load()?.bump
+    )]
+    pub oracle_aggregator: AccountLoader<'info, OracleAggregator>,
+    
+    #[account(
+        seeds = [b"governance"],
+        bump = governance.bump
+    )]
+    pub governance: Option<Account<'info, CadenGovernance>>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(asset_symbol: String, asset_type: AssetType)]
 pub struct UpdatePriceFromPyth<'info> {
     #[account(
         mut,
         seeds = [b"oracle_aggregator"],
-        bump = oracle_aggregator.bump
+        bump = oracle_aggregator.load()?.bump
+    )]
+    pub oracle_aggregator: AccountLoader<'info, OracleAggregator>,
+    
+    #[account(
+        seeds = [b"oracle_config"],
+        bump = oracle_config.bump
+    )]
+    pub oracle_config: Account<'info, OracleConfig>,
+    
+    #[account(
+        init_if_needed,
+        payer = updater,
+        space = PriceAccumulator::SPACE,
+        seeds = [b"price_accumulator", asset_symbol.as_bytes(), &[asset_type as u8]],
+        bump
+    )]
+    pub price_accumulator: Account<'info, PriceAccumulator>,
+    
+    #[account(
+        mut,
+        seeds = [b"price_history", asset_symbol.as_bytes(), &[asset_type as u8]],
+        bump = price_history.load()?.bump
     )]
-    pub oracle_aggregator: Account<'info, OracleAggregator>,
+    pub price_history: AccountLoader<'info, PriceHistory>,
     
-    /// CHECK: Pyth price account - validated in instruction
+    /// CHECK: Pyth price account - owner checked in oracle::read_pyth_price, key pinned per asset
     pub pyth_price_account: AccountInfo<'info>,
     
-    pub user: Signer<'info>,
+    #[account(
+        seeds = [b"oracle_updater", oracle_aggregator.key().as_ref(), updater.key().as_ref()],
+        bump = updater_role.bump
+    )]
+    pub updater_role: Option<Account<'info, OracleUpdater>>,
+    
+    #[account(mut)]
+    pub updater: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
+#[instruction(asset_symbol: String, asset_type: AssetType)]
 pub struct UpdatePriceFromSwitchboard<'info> {
     #[account(
         mut,
         seeds = [b"oracle_aggregator"],
-        bump = oracle_aggregator.bump
+        bump = oracle_aggregator.load()?.bump
+    )]
+    pub oracle_aggregator: AccountLoader<'info, OracleAggregator>,
+    
+    #[account(
+        seeds = [b"oracle_config"],
+        bump = oracle_config.bump
+    )]
+    pub oracle_config: Account<'info, OracleConfig>,
+    
+    #[account(
+        init_if_needed,
+        payer = updater,
+        space = PriceAccumulator::SPACE,
+        seeds = [b"price_accumulator", asset_symbol.as_bytes(), &[asset_type as u8]],
+        bump
+    )]
+    pub price_accumulator: Account<'info, PriceAccumulator>,
+    
+    #[account(
+        mut,
+        seeds = [b"price_history", asset_symbol.as_bytes(), &[asset_type as u8]],
+        bump = price_history.load()?.bump
     )]
-    pub oracle_aggregator: Account<'info, OracleAggregator>,
+    pub price_history: AccountLoader<'info, PriceHistory>,
     
-    /// CHECK: Switchboard aggregator account - validated in instruction
+    /// CHECK: Switchboard aggregator - owner checked in oracle::read_switchboard_price, key pinned per asset
     pub switchboard_aggregator: AccountInfo<'info>,
     
-    pub user: Signer<'info>,
+    #[account(
+        seeds = [b"oracle_updater", oracle_aggregator.key().as_ref(), updater.key().as_ref()],
+        bump = updater_role.bump
+    )]
+    pub updater_role: Option<Account<'info, OracleUpdater>>,
+    
+    #[account(mut)]
+    pub updater: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(publisher: Pubkey)]
+pub struct RegisterPricePublisher<'info> {
+    #[account(
+        seeds = [b"oracle_aggregator"],
+        bump = oracle_aggregator.load()?.bump
+    )]
+    pub oracle_aggregator: AccountLoader<'info, OracleAggregator>,
+    
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + 32 + 1 + 8 + 1 + 4 + MAX_UPDATER_ASSETS * (4 + 10) + 8 + 8 + 1 + 32 + 8 + 1, // PricePublisher size
+        seeds = [b"price_publisher", publisher.as_ref()],
+        bump
+    )]
+    pub price_publisher: Account<'info, PricePublisher>,
+    
+    #[account(
+        seeds = [b"governance"],
+        bump = governance.bump
+    )]
+    pub governance: Option<Account<'info, CadenGovernance>>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DeactivatePricePublisher<'info> {
+    #[account(
+        seeds = [b"oracle_aggregator"],
+        bump = oracle_aggregator.load()?.bump
+    )]
+    pub oracle_aggregator: AccountLoader<'info, OracleAggregator>,
+    
+    #[account(
+        mut,
+        seeds = [b"price_publisher", price_publisher.publisher.as_ref()],
+        bump = price_publisher.bump
+    )]
+    pub price_publisher: Account<'info, PricePublisher>,
+    
+    #[account(
+        seeds = [b"governance"],
+        bump = governance.bump
+    )]
+    pub governance: Option<Account<'info, CadenGovernance>>,
+    
+    pub authority: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct UpdatePriceFromExternal<'info> {
+#[instruction(attestation: PriceAttestation)]
+pub struct SubmitPriceAttestation<'info> {
     #[account(
         mut,
         seeds = [b"oracle_aggregator"],
-        bump = oracle_aggregator.bump
+        bump = oracle_aggregator.load()?.bump
+    )]
+    pub oracle_aggregator: AccountLoader<'info, OracleAggregator>,
+    
+    #[account(
+        mut,
+        seeds = [b"price_publisher", price_publisher.publisher.as_ref()],
+        bump = price_publisher.bump
+    )]
+    pub price_publisher: Account<'info, PricePublisher>,
+    
+    #[account(
+        seeds = [b"oracle_config"],
+        bump = oracle_config.bump
     )]
-    pub oracle_aggregator: Account<'info, OracleAggregator>,
+    pub oracle_config: Account<'info, OracleConfig>,
+    
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = PriceAccumulator::SPACE,
+        seeds = [b"price_accumulator", attestation.asset_symbol.as_bytes(), &[attestation.asset_type as u8]],
+        bump
+    )]
+    pub price_accumulator: Account<'info, PriceAccumulator>,
+    
+    #[account(
+        mut,
+        seeds = [b"price_history", attestation.asset_symbol.as_bytes(), &[attestation.asset_type as u8]],
+        bump = price_history.load()?.bump
+    )]
+    pub price_history: AccountLoader<'info, PriceHistory>,
+    
+    /// CHECK: Instructions sysvar, used to find the Ed25519 verification instruction
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
     
     #[account(mut)]
-    pub admin: Signer<'info>,
+    pub relayer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
@@ -2742,7 +6861,7 @@ pub enum ErrorCode {
     MarketNotActive,
     #[msg("Market is not settled")]
     MarketNotSettled,
-    #[msg("Oracle price move exceeds 5% limit")]
+    #[msg("Oracle price move exceeds the configured limit")]
     OracleMoveTooLarge,
     #[msg("Invalid leverage: must be between 1x and 3x")]
     InvalidLeverage,
@@ -2808,6 +6927,407 @@ pub enum ErrorCode {
     ExecutionDelayNotMet,
     #[msg("Invalid price data from oracle")]
     InvalidPriceData,
+    #[msg("Oracle price is stale")]
+    StalePrice,
+    #[msg("Price account does not match the configured oracle source")]
+    InvalidOracleAccount,
+    #[msg("Mock oracle is only available in localnet builds")]
+    MockOracleDisabled,
+    #[msg("Not enough price history for TWAP/EMA")]
+    TwapNotReady,
+    #[msg("Invalid price accumulator configuration")]
+    InvalidAccumulatorConfig,
+    #[msg("Market has not expired yet")]
+    MarketNotExpired,
+    #[msg("No recorded price at or before the requested slot")]
+    PriceHistoryUnavailable,
+    #[msg("Oracle is at capacity; grow the account first")]
+    OracleCapacityExceeded,
+    #[msg("New oracle capacity must exceed the current capacity")]
+    InvalidOracleCapacity,
+    #[msg("Updater is not scoped for this asset")]
+    UpdaterOutOfScope,
+    #[msg("Too many assets in updater scope")]
+    TooManyUpdaterAssets,
+    #[msg("Missing or invalid Ed25519 signature for price attestation")]
+    InvalidAttestationSignature,
+    #[msg("Price attestation nonce or timestamp already used")]
+    AttestationReplayed,
+    #[msg("Price attestation timestamp is invalid")]
+    InvalidAttestationTimestamp,
+    #[msg("Price publisher is not active")]
+    PublisherInactive,
+    #[msg("Price override needs both oracle admin and governance approval or must wait out its delay")]
+    PriceOverrideNotReady,
+    #[msg("Underlying market is closed")]
+    MarketClosed,
+    #[msg("Asset requires a trading schedule")]
+    TradingScheduleRequired,
+    #[msg("Invalid trading schedule")]
+    InvalidTradingSchedule,
+    #[msg("Heatmap was cranked too recently")]
+    CrankTooSoon,
+    #[msg("Heatmap source must be a settlement slot pool or CFD market")]
+    InvalidHeatmapSource,
+    #[msg("Amount must be greater than zero")]
+    InvalidAmount,
+    #[msg("Insufficient pool liquidity")]
+    InsufficientLiquidity,
+    #[msg("Pool registry is full")]
+    PoolRegistryFull,
+    #[msg("Settlement slot supply cap reached")]
+    SlotSupplyCapReached,
+    #[msg("T+0 slot pricing requires a supply cap")]
+    SupplyCapRequired,
+    #[msg("Auction must start before it ends")]
+    InvalidAuctionWindow,
+    #[msg("Auction is not running")]
+    AuctionNotActive,
+    #[msg("Auction is sold out")]
+    AuctionSoldOut,
+    #[msg("Auction has not ended")]
+    AuctionNotEnded,
+    #[msg("Settlement slot is for a different asset")]
+    SlotAssetMismatch,
+    #[msg("Settlement slot notional budget exhausted")]
+    SlotBudgetExhausted,
+    #[msg("Settlement slot has not expired")]
+    SlotNotExpired,
+    #[msg("Settlement slot is currently rented")]
+    SlotAlreadyRented,
+    #[msg("Invalid rental period")]
+    InvalidRentalPeriod,
+    #[msg("Fraction mint, token account and token program are required for a fractionalized slot")]
+    FractionAccountsRequired,
+    #[msg("All outstanding fractions must be returned to redeem the slot")]
+    FractionsOutstanding,
+    #[msg("Price account is not the feed pinned for this asset")]
+    PriceFeedAccountMismatch,
+    #[msg("Publisher is not registered for this asset")]
+    AttestationOutOfScope,
+    #[msg("Slot duration must be between 1 and 365 days")]
+    InvalidSlotDuration,
+    #[msg("Only unused settlement slots can be wrapped")]
+    SlotPartiallyUsed,
+    #[msg("Settlement slot is wrapped in a tenor vault; unwrap it first")]
+    SlotWrapped,
+}
+
+// Helper function to check the signer is the oracle admin or the governance admin
+fn authorize_oracle_authority(
+    oracle: &AccountInfo,
+    authority: &Pubkey,
+    governance: Option<&CadenGovernance>,
+) -> Result<()> {
+    let is_oracle_admin = oracle_admin(oracle)? == *authority;
+    let is_governance = governance.is_some_and(|g| g.admin == *authority);
+    require!(is_oracle_admin || is_governance, ErrorCode::Unauthorized);
+    Ok(())
+}
+
+// Helper function to build the OracleAggregator enabled_sources bitmask
+fn source_mask(sources: &[PriceSource]) -> u16 {
+    sources.iter().fold(0, |mask, source| mask | (1 << *source as u16))
+}
+
+// Helper function for the LP tokens a deposit earns: sqrt(a * b) on first deposit, minus a
+// permanently locked minimum so the share price can never be inflated from an empty pool;
+// otherwise the smaller pro-rata share. Returns (lp tokens to mint, lp tokens locked).
+fn pool_lp_tokens_for_deposit(
+    lp_supply: u64,
+    reserve_a: u64,
+    reserve_b: u64,
+    amount_a: u64,
+    amount_b: u64,
+) -> Result<(u64, u64)> {
+    let (lp_tokens, locked) = if lp_supply == 0 {
+        let initial = integer_sqrt(amount_a as u128 * amount_b as u128);
+        require!(initial > MINIMUM_POOL_LIQUIDITY, ErrorCode::InsufficientLiquidity);
+        (initial - MINIMUM_POOL_LIQUIDITY, MINIMUM_POOL_LIQUIDITY)
+    } else {
+        require!(reserve_a > 0 && reserve_b > 0, ErrorCode::InsufficientLiquidity);
+        let share_a = amount_a as u128 * lp_supply as u128 / reserve_a as u128;
+        let share_b = amount_b as u128 * lp_supply as u128 / reserve_b as u128;
+        (u64::try_from(share_a.min(share_b)).map_err(|_| ErrorCode::MathOverflow)?, 0)
+    };
+    require!(lp_tokens > 0, ErrorCode::InsufficientLiquidity);
+    Ok((lp_tokens, locked))
+}
+
+// Helper function for a constant product (x * y = k) swap with a fixed input.
+// The fee is taken from the input and stays in the pool. Returns (amount_out, fee).
+fn pool_swap_exact_in(reserve_in: u64, reserve_out: u64, amount_in: u64, fee_rate: u16) -> Result<(u64, u64)> {
+    require!(reserve_in > 0 && reserve_out > 0, ErrorCode::InsufficientLiquidity);
+    let fee = (amount_in as u128 * fee_rate as u128 / 10000) as u64;
+    let amount_in_after_fee = (amount_in - fee) as u128;
+    let amount_out = reserve_out as u128 * amount_in_after_fee / (reserve_in as u128 + amount_in_after_fee);
+    require!(amount_out > 0, ErrorCode::InsufficientLiquidity);
+    Ok((amount_out as u64, fee))
+}
+
+// Helper function for a constant product swap with a fixed output, rounding the input up
+// so the pool never loses value. Returns (amount_in including fee, fee).
+fn pool_swap_exact_out(reserve_in: u64, reserve_out: u64, amount_out: u64, fee_rate: u16) -> Result<(u64, u64)> {
+    require!(reserve_in > 0 && amount_out < reserve_out, ErrorCode::InsufficientLiquidity);
+    let numerator = reserve_in as u128 * amount_out as u128;
+    let amount_in_after_fee = numerator.div_ceil((reserve_out - amount_out) as u128);
+    let amount_in = amount_in_after_fee
+        .checked_mul(10000)
+        .ok_or(ErrorCode::MathOverflow)?
+        .div_ceil(10000 - fee_rate as u128);
+    let amount_in = u64::try_from(amount_in).map_err(|_| ErrorCode::MathOverflow)?;
+    Ok((amount_in, amount_in - amount_in_after_fee as u64))
+}
+
+// Helper function to move a pool swap's tokens: `input` (from, to, amount) is signed by the
+// user, `output` (from, to, amount) by the pool authority PDA
+fn pool_swap_transfers<'info>(
+    token_program: &Program<'info, Token>,
+    user: &Signer<'info>,
+    pool_authority: &SystemAccount<'info>,
+    pool_key: &Pubkey,
+    pool_authority_bump: u8,
+    input: (&Account<'info, TokenAccount>, &Account<'info, TokenAccount>, u64),
+    output: (&Account<'info, TokenAccount>, &Account<'info, TokenAccount>, u64),
+) -> Result<()> {
+    anchor_spl::token::transfer(
+        CpiContext::new(
+            token_program.to_account_info(),
+            Transfer {
+                from: input.0.to_account_info(),
+                to: input.1.to_account_info(),
+                authority: user.to_account_info(),
+            },
+        ),
+        input.2,
+    )?;
+    
+    let seeds = &[
+        b"pool_authority",
+        pool_key.as_ref(),
+        &[pool_authority_bump],
+    ];
+    let signer = &[&seeds[..]];
+    anchor_spl::token::transfer(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            Transfer {
+                from: output.0.to_account_info(),
+                to: output.1.to_account_info(),
+                authority: pool_authority.to_account_info(),
+            },
+            signer,
+        ),
+        output.2,
+    )
+}
+
+// Helper function to fill in a newly created SettlementSlot and issue its supply-1 NFT
+// to `holder_token_account`; whoever holds that token owns the slot
+fn issue_settlement_slot<'info>(
+    settlement_slot: &mut Account<'info, SettlementSlot>,
+    terms: SlotTerms,
+    slot_mint: &AccountInfo<'info>,
+    holder_token_account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    token_2022_program: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    settlement_slot.slot_id = terms.slot_id;
+    settlement_slot.asset_symbol = terms.asset_symbol;
+    settlement_slot.asset_type = terms.asset_type;
+    settlement_slot.settlement_time = terms.settlement_time;
+    settlement_slot.slot_duration = terms.slot_duration;
+    settlement_slot.creator = terms.creator;
+    settlement_slot.mint = slot_mint.key();
+    settlement_slot.mint_price = terms.mint_price;
+    settlement_slot.created_slot = clock.slot;
+    settlement_slot.created_timestamp = clock.unix_timestamp;
+    settlement_slot.expiry_timestamp = terms.expiry_timestamp;
+    settlement_slot.notional_budget = terms.notional_budget;
+    settlement_slot.notional_used = 0;
+    settlement_slot.is_tradable = true;
+    settlement_slot.is_active = true;
+    settlement_slot.bump = terms.bump;
+    
+    let seed_bytes = terms.slot_id.to_le_bytes();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"settlement_slot",
+        &seed_bytes,
+        &[terms.bump],
+    ]];
+    let slot_authority = settlement_slot.to_account_info();
+    init_slot_metadata(
+        token_2022_program,
+        slot_mint,
+        &slot_authority,
+        payer,
+        system_program,
+        settlement_slot,
+        signer_seeds,
+    )?;
+    mint_slot_token(token_2022_program, slot_mint, holder_token_account, &slot_authority, signer_seeds)
+}
+
+// Helper function to charge `notional` against a slot's instant settlement budget,
+// deactivating the slot once the budget is used up
+fn consume_slot_notional(slot: &mut SettlementSlot, notional: u64) -> Result<()> {
+    if slot.notional_budget == 0 {
+        return Ok(());
+    }
+    let used = slot.notional_used.checked_add(notional).ok_or(ErrorCode::MathOverflow)?;
+    require!(used <= slot.notional_budget, ErrorCode::SlotBudgetExhausted);
+    slot.notional_used = used;
+    if used == slot.notional_budget {
+        slot.is_active = false;
+    }
+    Ok(())
+}
+
+// Helper function for the current Dutch auction price: linear decay from the start price, held at the floor
+fn auction_price(auction: &SlotAuction, now: i64) -> u64 {
+    let elapsed = now.saturating_sub(auction.start_timestamp).max(0) as u64;
+    let decay = auction.decay_per_second.saturating_mul(elapsed);
+    auction.start_price.saturating_sub(decay).max(auction.floor_price)
+}
+
+// Helper function for the slots counted against a pricing curve's supply cap
+fn slot_supply_committed(pricing: &SlotPricing) -> u64 {
+    pricing.outstanding.saturating_add(pricing.reserved)
+}
+
+// Helper function for the current mint price on a slot pricing curve
+fn slot_curve_price(pricing: &SlotPricing) -> Result<u64> {
+    Ok(pricing
+        .price_step
+        .checked_mul(pricing.outstanding)
+        .and_then(|premium| premium.checked_add(pricing.base_price))
+        .ok_or(ErrorCode::MathOverflow)?)
+}
+
+// Helper function to pay for a settlement slot in USDC: the protocol royalty goes to the fee
+// vault and the remainder to the seller. Returns the royalty taken.
+fn pay_for_slot<'info>(
+    token_program: &Program<'info, Token>,
+    buyer: &Signer<'info>,
+    buyer_usdc: &Account<'info, TokenAccount>,
+    seller_usdc: &Account<'info, TokenAccount>,
+    protocol_fee_vault: &Account<'info, TokenAccount>,
+    price: u64,
+    royalty_bps: u16,
+) -> Result<u64> {
+    let royalty = (price as u128 * royalty_bps as u128 / 10_000) as u64;
+    for (to, amount) in [(seller_usdc, price - royalty), (protocol_fee_vault, royalty)] {
+        if amount > 0 {
+            anchor_spl::token::transfer(
+                CpiContext::new(
+                    token_program.to_account_info(),
+                    Transfer {
+                        from: buyer_usdc.to_account_info(),
+                        to: to.to_account_info(),
+                        authority: buyer.to_account_info(),
+                    },
+                ),
+                amount,
+            )?;
+        }
+    }
+    Ok(royalty)
+}
+
+// Helper function to hand an escrowed slot token to `recipient` and close the escrow account,
+// refunding its rent to `rent_to`. `escrow_authority` is the listing or rental PDA that owns
+// the escrow and `authority_seeds` its signer seeds; that PDA is closed by its own constraint.
+fn release_slot_escrow<'info>(
+    token_2022_program: &Program<'info, Token2022>,
+    slot_mint: &InterfaceAccount<'info, token_interface::Mint>,
+    escrow: &InterfaceAccount<'info, token_interface::TokenAccount>,
+    recipient: &InterfaceAccount<'info, token_interface::TokenAccount>,
+    escrow_authority: &AccountInfo<'info>,
+    authority_seeds: &[&[u8]],
+    rent_to: &AccountInfo<'info>,
+) -> Result<()> {
+    transfer_slot_token(
+        &token_2022_program.to_account_info(),
+        &slot_mint.to_account_info(),
+        &escrow.to_account_info(),
+        &recipient.to_account_info(),
+        escrow_authority,
+        &[authority_seeds],
+    )?;
+    close_slot_escrow(token_2022_program, escrow, escrow_authority, authority_seeds, rent_to)
+}
+
+// Helper function to close an empty slot escrow token account, signed by its escrow PDA
+fn close_slot_escrow<'info>(
+    token_2022_program: &Program<'info, Token2022>,
+    escrow: &InterfaceAccount<'info, token_interface::TokenAccount>,
+    escrow_authority: &AccountInfo<'info>,
+    authority_seeds: &[&[u8]],
+    rent_to: &AccountInfo<'info>,
+) -> Result<()> {
+    token_interface::close_account(CpiContext::new_with_signer(
+        token_2022_program.to_account_info(),
+        CloseAccount {
+            account: escrow.to_account_info(),
+            destination: rent_to.clone(),
+            authority: escrow_authority.clone(),
+        },
+        &[authority_seeds],
+    ))
+}
+
+// Helper function to close one of our accounts that is not a typed account in the
+// instruction's context, as Anchor's `close` constraint would
+fn close_program_account<'info>(account: &AccountInfo<'info>, rent_to: &AccountInfo<'info>) -> Result<()> {
+    let lamports = account.lamports();
+    **account.try_borrow_mut_lamports()? = 0;
+    **rent_to.try_borrow_mut_lamports()? += lamports;
+    account.assign(&anchor_lang::system_program::ID);
+    account.resize(0)?;
+    Ok(())
+}
+
+// Helper function to recognise a slot holder that is one of our escrows. Returns the escrow's
+// seed prefix and bump and the seller, lender or owner it refunds, or None for a wallet holder.
+fn slot_escrow_holder(holder: &AccountInfo, settlement_slot: &Pubkey) -> Result<Option<(&'static [u8], u8, Pubkey)>> {
+    if *holder.owner != crate::ID {
+        return Ok(None);
+    }
+    let data = holder.try_borrow_data()?;
+    let (prefix, escrowed_slot, bump, beneficiary): (&'static [u8], _, _, _) =
+        if data.starts_with(SlotListing::DISCRIMINATOR) {
+            let listing = SlotListing::try_deserialize(&mut &data[..])?;
+            (b"slot_listing", listing.settlement_slot, listing.bump, listing.seller)
+        } else if data.starts_with(SlotRental::DISCRIMINATOR) {
+            let rental = SlotRental::try_deserialize(&mut &data[..])?;
+            (b"slot_rental", rental.settlement_slot, rental.bump, rental.lender)
+        } else if data.starts_with(FractionVault::DISCRIMINATOR) {
+            let vault = FractionVault::try_deserialize(&mut &data[..])?;
+            (b"fraction_vault", vault.settlement_slot, vault.bump, vault.owner)
+        } else if data.starts_with(TenorVault::DISCRIMINATOR) {
+            // Sweeping a wrapped slot would leave a tenor token with nothing behind it
+            return err!(ErrorCode::SlotWrapped);
+        } else {
+            return err!(ErrorCode::InvalidSettlementSlot);
+        };
+    require_keys_eq!(escrowed_slot, *settlement_slot, ErrorCode::InvalidSettlementSlot);
+    Ok(Some((prefix, bump, beneficiary)))
+}
+
+// Helper function for the integer square root (floor) used to size first LP deposits
+fn integer_sqrt(value: u128) -> u64 {
+    if value < 2 {
+        return value as u64;
+    }
+    let mut x = value;
+    let mut y = x.div_ceil(2);
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+    x as u64
 }
 
 // Helper function to calculate aggregated price from multiple sources
@@ -2841,5 +7361,180 @@ fn calculate_aggregated_price(pyth_price: u64, switchboard_price: u64, external_
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integer_sqrt_floors() {
+        assert_eq!(integer_sqrt(0), 0);
+        assert_eq!(integer_sqrt(1), 1);
+        assert_eq!(integer_sqrt(2), 1);
+        assert_eq!(integer_sqrt(15), 3);
+        assert_eq!(integer_sqrt(16), 4);
+        assert_eq!(integer_sqrt(1_000_000 * 1_000_000 - 1), 999_999);
+        assert_eq!(integer_sqrt(u64::MAX as u128 * u64::MAX as u128), u64::MAX);
+        assert_eq!(integer_sqrt(u128::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn first_deposit_locks_minimum_liquidity() {
+        let (minted, locked) = pool_lp_tokens_for_deposit(0, 0, 0, 4_000_000, 1_000_000).unwrap();
+        assert_eq!((minted, locked), (2_000_000 - MINIMUM_POOL_LIQUIDITY, MINIMUM_POOL_LIQUIDITY));
 
+        // A deposit worth no more than the locked minimum is refused
+        assert!(pool_lp_tokens_for_deposit(0, 0, 0, 1_000, 1_000).is_err());
+        assert!(pool_lp_tokens_for_deposit(0, 0, 0, 1_001, 1_001).is_ok());
+    }
+
+    #[test]
+    fn later_deposits_earn_the_smaller_pro_rata_share() {
+        // Pool of 4 USDC / 1 tenor token with 2 LP tokens outstanding
+        let (minted, locked) = pool_lp_tokens_for_deposit(2_000_000, 4_000_000, 1_000_000, 2_000_000, 1_000_000).unwrap();
+        assert_eq!((minted, locked), (1_000_000, 0));
+
+        // Depositing too little of either side to earn a single LP token is refused
+        assert!(pool_lp_tokens_for_deposit(2_000_000, 4_000_000, 1_000_000, 1, 1).is_err());
+        assert!(pool_lp_tokens_for_deposit(2_000_000, 0, 1_000_000, 1_000, 1_000).is_err());
+    }
+
+    #[test]
+    fn swap_exact_in_follows_the_constant_product() {
+        assert_eq!(pool_swap_exact_in(1_000_000, 1_000_000, 1_000_000, 0).unwrap(), (500_000, 0));
+
+        // 0.3% fee comes off the input and stays in the pool
+        let (out, fee) = pool_swap_exact_in(1_000_000, 1_000_000, 1_000_000, 30).unwrap();
+        assert_eq!(fee, 3_000);
+        assert_eq!(out, 1_000_000 * 997_000 / 1_997_000);
+
+        // k never decreases
+        let k = 1_000_000u128 * 1_000_000;
+        assert!((1_000_000 + 1_000_000) as u128 * (1_000_000 - out) as u128 >= k);
+    }
+
+    #[test]
+    fn swap_exact_in_rejects_empty_pools_and_dust() {
+        assert!(pool_swap_exact_in(0, 1_000_000, 1_000, 30).is_err());
+        assert!(pool_swap_exact_in(1_000_000, 0, 1_000, 30).is_err());
+        // Too small to buy a single unit
+        assert!(pool_swap_exact_in(1_000_000_000, 1_000, 1, 0).is_err());
+    }
+
+    #[test]
+    fn swap_exact_in_handles_full_range_reserves() {
+        let (out, _) = pool_swap_exact_in(u64::MAX, u64::MAX, u64::MAX, 0).unwrap();
+        assert_eq!(out, u64::MAX / 2);
+    }
+
+    #[test]
+    fn swap_exact_out_rounds_the_input_up() {
+        // 1 * 3 / (10 - 3) = 0.43 rounds up to 1, never letting the trader underpay
+        assert_eq!(pool_swap_exact_out(1, 10, 3, 0).unwrap(), (1, 0));
+        assert_eq!(pool_swap_exact_out(1_000_000, 1_000_000, 500_000, 0).unwrap(), (1_000_000, 0));
+
+        let (amount_in, fee) = pool_swap_exact_out(1_000_000, 1_000_000, 500_000, 30).unwrap();
+        assert_eq!(amount_in, (1_000_000u128 * 10_000).div_ceil(9_970) as u64);
+        assert_eq!(fee, amount_in - 1_000_000);
+
+        // Feeding the input back through exact-in yields at least the requested output
+        let (out, _) = pool_swap_exact_in(1_000_000, 1_000_000, amount_in, 30).unwrap();
+        assert!(out >= 500_000);
+    }
 
+    #[test]
+    fn swap_exact_out_cannot_drain_the_pool() {
+        assert!(pool_swap_exact_out(1_000_000, 1_000_000, 1_000_000, 30).is_err());
+        assert!(pool_swap_exact_out(0, 1_000_000, 1, 30).is_err());
+    }
+
+    #[test]
+    fn swap_exact_out_reports_overflow() {
+        // The required input exceeds u64
+        assert!(pool_swap_exact_out(u64::MAX, u64::MAX, u64::MAX - 1, 0).is_err());
+    }
+
+    #[test]
+    fn auction_reservations_count_against_the_cap_but_not_the_curve() {
+        let mut pricing = SlotPricing {
+            asset_symbol: "AAPL".to_string(),
+            asset_type: AssetType::Stock,
+            settlement_time: 0,
+            base_price: 100_000_000,
+            price_step: 10_000_000,
+            max_supply: 10,
+            notional_budget: 1_000_000_000,
+            outstanding: 2,
+            reserved: 5,
+            total_minted: 2,
+            total_revenue: 0,
+            bump: 0,
+        };
+        assert_eq!(slot_supply_committed(&pricing), 7);
+        assert_eq!(slot_curve_price(&pricing).unwrap(), 120_000_000);
+
+        pricing.reserved = u64::MAX;
+        assert_eq!(slot_supply_committed(&pricing), u64::MAX);
+    }
+
+    #[test]
+    fn auction_price_decays_per_second_to_the_floor() {
+        let auction = SlotAuction {
+            asset_symbol: "BTC".to_string(),
+            asset_type: AssetType::Crypto,
+            epoch: 0,
+            total_slots: 1,
+            sold: 0,
+            slot_duration: 7,
+            start_price: 10_000_000,
+            floor_price: 2_000_000,
+            decay_per_second: 10_000,
+            start_timestamp: 1_000,
+            end_timestamp: 2_000,
+            notional_budget: 1,
+            revenue: 0,
+            is_finalized: false,
+            bump: 0,
+        };
+        assert_eq!(auction_price(&auction, 500), 10_000_000);
+        assert_eq!(auction_price(&auction, 1_000), 10_000_000);
+        assert_eq!(auction_price(&auction, 1_100), 9_000_000);
+        assert_eq!(auction_price(&auction, 1_999), 2_000_000);
+        assert_eq!(auction_price(&auction, i64::MAX), 2_000_000);
+    }
+
+    #[test]
+    fn account_spaces_fit_the_longest_symbol() {
+        let market = Market {
+            t0_price: u64::MAX,
+            t2_price: u64::MAX,
+            opened_slot: u64::MAX,
+            status: MarketStatus::Settled,
+            usdc_vault: Pubkey::default(),
+            bump: 0,
+            asset_symbol: "A".repeat(10),
+            asset_type: AssetType::Stock,
+            entry_price_mode: PriceMode::Spot,
+            liquidation_price_mode: PriceMode::Twap,
+            settlement_price_mode: PriceMode::Ema,
+            expiry_timestamp: i64::MAX,
+            settlement_days: u64::MAX,
+        };
+        assert_eq!(8 + market.try_to_vec().unwrap().len(), Market::SPACE);
+
+        let accumulator = PriceAccumulator {
+            asset_symbol: "A".repeat(10),
+            asset_type: AssetType::Crypto,
+            last_price: 0,
+            last_slot: 0,
+            cumulative_price: 0,
+            window_start_slot: 0,
+            window_start_cumulative: 0,
+            twap_price: 0,
+            twap_window_slots: 0,
+            ema_price: 0,
+            ema_period_slots: 0,
+            bump: 0,
+        };
+        assert_eq!(8 + accumulator.try_to_vec().unwrap().len(), PriceAccumulator::SPACE);
+    }
+}
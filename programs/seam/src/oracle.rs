@@ -0,0 +1,862 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::Discriminator;
+use pyth_sdk_solana::state::SolanaPriceAccount;
+
+use crate::price_table::{find_entry, find_or_insert, load_table, load_table_mut};
+use crate::{
+    AssetPrice, AssetType, ErrorCode, MultiAssetOracle, OracleAggregator, OracleConfig, OracleMock,
+    OracleMode, OracleUpdater, PriceAccumulator, PriceFeed, PriceHistory, PriceHistoryEntry,
+    PriceMode, PriceSource, MAX_PULLED_PRICE_AGE_SECS, PRICE_HISTORY_LEN, PYTH_PROGRAM_IDS,
+    SWITCHBOARD_PROGRAM_ID, SYMBOL_LEN,
+};
+
+/// A single price observation, normalized to 6 decimals
+#[derive(Clone, Copy, Debug)]
+pub struct PriceReading {
+    pub price: u64,         // Price in 6 decimals
+    pub confidence: u64,    // Confidence interval (0 if the source has none)
+    pub slot: u64,          // Slot the price was written
+    pub source: PriceSource, // Where the price came from
+}
+
+/// Common interface over every on-chain price account the program can read
+pub trait PriceReader {
+    fn read_price(&self, asset_symbol: &str, asset_type: AssetType) -> Result<PriceReading>;
+}
+
+impl PriceReader for OracleMock {
+    // The mock carries a single asset's price
+    fn read_price(&self, asset_symbol: &str, asset_type: AssetType) -> Result<PriceReading> {
+        require!(
+            self.asset_symbol == asset_symbol && self.asset_type == asset_type,
+            ErrorCode::AssetNotFound
+        );
+        Ok(PriceReading {
+            price: self.price,
+            confidence: 0,
+            slot: self.updated_slot,
+            source: PriceSource::Manual,
+        })
+    }
+}
+
+/// View of the multi-asset oracle's asset table
+pub struct MultiAssetPrices<'a> {
+    pub asset_prices: &'a [AssetPrice],
+}
+
+impl PriceReader for MultiAssetPrices<'_> {
+    fn read_price(&self, asset_symbol: &str, asset_type: AssetType) -> Result<PriceReading> {
+        let index = find_entry(self.asset_prices, &encode_symbol(asset_symbol), asset_type as u8)
+            .ok_or(ErrorCode::AssetNotFound)?;
+        let asset_price = &self.asset_prices[index];
+
+        Ok(PriceReading {
+            price: asset_price.price,
+            confidence: asset_price.confidence,
+            slot: asset_price.last_updated,
+            source: PriceSource::from_u8(asset_price.source),
+        })
+    }
+}
+
+/// View of the aggregator's feed table restricted to one of its price columns
+pub struct AggregatorFeed<'a> {
+    pub price_feeds: &'a [PriceFeed],
+    pub source: PriceSource,
+}
+
+impl PriceReader for AggregatorFeed<'_> {
+    fn read_price(&self, asset_symbol: &str, asset_type: AssetType) -> Result<PriceReading> {
+        let index = find_entry(self.price_feeds, &encode_symbol(asset_symbol), asset_type as u8)
+            .ok_or(ErrorCode::AssetNotFound)?;
+        let feed = &self.price_feeds[index];
+
+        let price = match self.source {
+            PriceSource::Pyth => feed.pyth_price,
+            PriceSource::Switchboard => feed.switchboard_price,
+            PriceSource::Aggregated => feed.aggregated_price,
+            _ => return err!(ErrorCode::InvalidPriceSource),
+        };
+        require!(feed.is_stale == 0, ErrorCode::StalePrice);
+
+        Ok(PriceReading {
+            price,
+            confidence: 0,
+            slot: feed.last_updated,
+            source: self.source,
+        })
+    }
+}
+
+/// Convert `value * 10^expo` to a price with 6 decimals
+fn to_price_decimals(value: i128, expo: i32) -> Result<u64> {
+    let shift = expo + 6;
+    let scaled = if shift >= 0 {
+        10i128.checked_pow(shift as u32).and_then(|factor| value.checked_mul(factor))
+    } else {
+        10i128.checked_pow(shift.unsigned_abs()).map(|factor| value / factor)
+    };
+    scaled
+        .and_then(|price| u64::try_from(price).ok())
+        .ok_or(error!(ErrorCode::InvalidPriceData))
+}
+
+/// Read a Pyth push-oracle price account. The account must be owned by the Pyth program
+/// and its price published within MAX_PULLED_PRICE_AGE_SECS of the cluster clock.
+pub fn read_pyth_price(price_account: &AccountInfo, clock: &Clock) -> Result<PriceReading> {
+    require!(PYTH_PROGRAM_IDS.contains(price_account.owner), ErrorCode::InvalidOracleAccount);
+    let price_feed = SolanaPriceAccount::account_info_to_feed(price_account)
+        .map_err(|_| error!(ErrorCode::InvalidPriceData))?;
+    let price = price_feed
+        .get_price_no_older_than(clock.unix_timestamp, MAX_PULLED_PRICE_AGE_SECS)
+        .ok_or(ErrorCode::StalePrice)?;
+    require!(price.price > 0, ErrorCode::InvalidPriceData);
+
+    Ok(PriceReading {
+        price: to_price_decimals(price.price as i128, price.expo)?,
+        confidence: to_price_decimals(price.conf as i128, price.expo)?,
+        slot: clock.slot,
+        source: PriceSource::Pyth,
+    })
+}
+
+// Switchboard v2 AggregatorAccountData is packed; these are byte offsets (discriminator
+// included) of the fields of latest_confirmed_round that we read
+const SWITCHBOARD_NUM_SUCCESS_OFFSET: usize = 341;
+const SWITCHBOARD_ROUND_OPEN_TIMESTAMP_OFFSET: usize = 358;
+const SWITCHBOARD_RESULT_MANTISSA_OFFSET: usize = 366;
+const SWITCHBOARD_RESULT_SCALE_OFFSET: usize = 382;
+
+/// Read the latest confirmed round of a Switchboard v2 aggregator. The account must be
+/// owned by the Switchboard program and the round opened within MAX_PULLED_PRICE_AGE_SECS.
+pub fn read_switchboard_price(aggregator: &AccountInfo, clock: &Clock) -> Result<PriceReading> {
+    require_keys_eq!(*aggregator.owner, SWITCHBOARD_PROGRAM_ID, ErrorCode::InvalidOracleAccount);
+    let data = aggregator.try_borrow_data()?;
+    require!(data.len() >= SWITCHBOARD_RESULT_SCALE_OFFSET + 4, ErrorCode::InvalidOracleAccount);
+    require!(
+        data[..8] == hash(b"account:AggregatorAccountData").to_bytes()[..8],
+        ErrorCode::InvalidOracleAccount
+    );
+
+    let read_bytes = |offset: usize, len: usize| &data[offset..offset + len];
+    let num_success = u32::from_le_bytes(read_bytes(SWITCHBOARD_NUM_SUCCESS_OFFSET, 4).try_into().unwrap());
+    let round_open_timestamp =
+        i64::from_le_bytes(read_bytes(SWITCHBOARD_ROUND_OPEN_TIMESTAMP_OFFSET, 8).try_into().unwrap());
+    let mantissa = i128::from_le_bytes(read_bytes(SWITCHBOARD_RESULT_MANTISSA_OFFSET, 16).try_into().unwrap());
+    let scale = u32::from_le_bytes(read_bytes(SWITCHBOARD_RESULT_SCALE_OFFSET, 4).try_into().unwrap());
+
+    require!(num_success > 0 && mantissa > 0, ErrorCode::InvalidPriceData);
+    require!(
+        clock.unix_timestamp.saturating_sub(round_open_timestamp) <= MAX_PULLED_PRICE_AGE_SECS as i64,
+        ErrorCode::StalePrice
+    );
+
+    Ok(PriceReading {
+        price: to_price_decimals(mantissa, -(scale as i32))?,
+        confidence: 0,
+        slot: clock.slot,
+        source: PriceSource::Switchboard,
+    })
+}
+
+/// Deserialize a program-owned price account, checking owner and discriminator
+fn deserialize_price_account<T: AccountDeserialize>(price_account: &AccountInfo) -> Result<T> {
+    require_keys_eq!(*price_account.owner, crate::ID, ErrorCode::InvalidOracleAccount);
+    let data = price_account.try_borrow_data()?;
+    T::try_deserialize(&mut &data[..]).map_err(|_| error!(ErrorCode::InvalidOracleAccount))
+}
+
+/// The mock oracle is only usable in builds compiled for localnet
+pub fn validate_oracle_mode(mode: OracleMode) -> Result<()> {
+    if mode == OracleMode::Mock {
+        require!(cfg!(feature = "localnet"), ErrorCode::MockOracleDisabled);
+    }
+    Ok(())
+}
+
+/// Read a price for `asset_symbol` from whichever source `config` selects.
+/// `price_account` must be the account backing that source.
+pub fn load_price(
+    config: &OracleConfig,
+    price_account: &AccountInfo,
+    asset_symbol: &str,
+    asset_type: AssetType,
+) -> Result<PriceReading> {
+    validate_oracle_mode(config.mode)?;
+
+    let reading = match config.mode {
+        OracleMode::Mock => {
+            let oracle: OracleMock = deserialize_price_account(price_account)?;
+            oracle.read_price(asset_symbol, asset_type)?
+        }
+        OracleMode::MultiAsset => {
+            let (_, asset_prices) = load_table::<MultiAssetOracle, AssetPrice>(price_account)?;
+            MultiAssetPrices { asset_prices: &asset_prices }.read_price(asset_symbol, asset_type)?
+        }
+        OracleMode::Pyth | OracleMode::Switchboard | OracleMode::Aggregated => {
+            let (_, price_feeds) = load_table::<OracleAggregator, PriceFeed>(price_account)?;
+            let source = match config.mode {
+                OracleMode::Pyth => PriceSource::Pyth,
+                OracleMode::Switchboard => PriceSource::Switchboard,
+                _ => PriceSource::Aggregated,
+            };
+            AggregatorFeed { price_feeds: &price_feeds, source }.read_price(asset_symbol, asset_type)?
+        }
+    };
+
+    require!(reading.price > 0, ErrorCode::InvalidPriceData);
+
+    // 0 disables the staleness check (e.g. for localnet)
+    if config.max_staleness_slots > 0 {
+        let clock = Clock::get()?;
+        require!(
+            clock.slot.saturating_sub(reading.slot) <= config.max_staleness_slots,
+            ErrorCode::StalePrice
+        );
+    }
+
+    Ok(reading)
+}
+
+/// Default TWAP window for new accumulators (~1 minute of slots)
+pub const DEFAULT_TWAP_WINDOW_SLOTS: u64 = 150;
+/// Default EMA period for new accumulators (~1 minute of slots)
+pub const DEFAULT_EMA_PERIOD_SLOTS: u64 = 150;
+
+/// Set up a freshly created accumulator; no-op if it has already been written
+pub fn init_accumulator(
+    accumulator: &mut PriceAccumulator,
+    asset_symbol: &str,
+    asset_type: AssetType,
+    bump: u8,
+) {
+    if accumulator.asset_symbol.is_empty() {
+        accumulator.asset_symbol = asset_symbol.to_string();
+        accumulator.asset_type = asset_type;
+        accumulator.twap_window_slots = DEFAULT_TWAP_WINDOW_SLOTS;
+        accumulator.ema_period_slots = DEFAULT_EMA_PERIOD_SLOTS;
+        accumulator.bump = bump;
+    }
+}
+
+// Fixed-point one for EMA decay factors
+const EMA_ONE: u128 = 1_000_000_000_000;
+
+// Weight the EMA keeps on its previous value after `elapsed` slots: (1 - 1/period)^elapsed,
+// i.e. a per-slot smoothing factor of 1/period, in EMA_ONE fixed point
+fn ema_decay(elapsed: u64, period: u64) -> u128 {
+    let mut base = EMA_ONE - EMA_ONE / period as u128;
+    let mut exponent = elapsed;
+    let mut decay = EMA_ONE;
+    while exponent > 0 && decay > 0 {
+        if exponent & 1 == 1 {
+            decay = decay * base / EMA_ONE;
+        }
+        base = base * base / EMA_ONE;
+        exponent >>= 1;
+    }
+    decay
+}
+
+// Move `ema` towards `price` as if `price` had been held for `elapsed` slots
+fn fold_ema(ema: u64, price: u64, elapsed: u64, period: u64) -> u64 {
+    if period == 0 {
+        return price;
+    }
+    let decay = ema_decay(elapsed, period);
+    ((ema as u128 * decay + price as u128 * (EMA_ONE - decay)) / EMA_ONE) as u64
+}
+
+/// Fold an oracle write into the TWAP/EMA accumulator.
+/// The previous price is weighted by how long it was live, so a single print
+/// carries no weight until time passes.
+pub fn record_price(accumulator: &mut PriceAccumulator, price: u64, slot: u64) {
+    if accumulator.last_slot == 0 {
+        accumulator.last_price = price;
+        accumulator.last_slot = slot;
+        accumulator.ema_price = price;
+        accumulator.window_start_slot = slot;
+        return;
+    }
+
+    let elapsed = slot.saturating_sub(accumulator.last_slot);
+    accumulator.cumulative_price += accumulator.last_price as u128 * elapsed as u128;
+    accumulator.ema_price = fold_ema(
+        accumulator.ema_price,
+        accumulator.last_price,
+        elapsed,
+        accumulator.ema_period_slots,
+    );
+
+    // Close the TWAP window once it has run its full length
+    let window_span = slot.saturating_sub(accumulator.window_start_slot);
+    if window_span > 0 && window_span >= accumulator.twap_window_slots {
+        accumulator.twap_price = ((accumulator.cumulative_price - accumulator.window_start_cumulative)
+            / window_span as u128) as u64;
+        accumulator.window_start_slot = slot;
+        accumulator.window_start_cumulative = accumulator.cumulative_price;
+    }
+
+    accumulator.last_price = price;
+    accumulator.last_slot = slot;
+}
+
+/// Fold a write into the asset's accumulator only if it changed the price the configured
+/// mode trades on, so other sources never move the TWAP/EMA. `written` pairs each mode
+/// the write fed with the price that mode now reads.
+pub fn record_active_price(
+    accumulator: &mut PriceAccumulator,
+    config: &OracleConfig,
+    written: &[(OracleMode, u64)],
+    slot: u64,
+) {
+    if let Some(&(_, price)) = written.iter().find(|(mode, _)| *mode == config.mode) {
+        record_price(accumulator, price, slot);
+    }
+}
+
+/// Read the TWAP or EMA as of `slot`, folding in time since the last write
+pub fn accumulated_price(accumulator: &PriceAccumulator, mode: PriceMode, slot: u64) -> Result<u64> {
+    require!(accumulator.last_slot > 0, ErrorCode::TwapNotReady);
+    let elapsed = slot.saturating_sub(accumulator.last_slot);
+
+    let price = match mode {
+        PriceMode::Spot => accumulator.last_price,
+        PriceMode::Ema => fold_ema(
+            accumulator.ema_price,
+            accumulator.last_price,
+            elapsed,
+            accumulator.ema_period_slots,
+        ),
+        PriceMode::Twap => {
+            let window_span = slot.saturating_sub(accumulator.window_start_slot);
+            if window_span > 0 && window_span >= accumulator.twap_window_slots {
+                let cumulative = accumulator.cumulative_price
+                    + accumulator.last_price as u128 * elapsed as u128;
+                ((cumulative - accumulator.window_start_cumulative) / window_span as u128) as u64
+            } else {
+                // Fall back to the last completed window
+                accumulator.twap_price
+            }
+        }
+    };
+
+    require!(price > 0, ErrorCode::TwapNotReady);
+    Ok(price)
+}
+
+/// Read a market price in the requested mode: spot from the configured source,
+/// or TWAP/EMA from the asset's accumulator
+pub fn load_market_price(
+    config: &OracleConfig,
+    price_account: &AccountInfo,
+    accumulator: &PriceAccumulator,
+    asset_symbol: &str,
+    asset_type: AssetType,
+    mode: PriceMode,
+) -> Result<u64> {
+    if mode == PriceMode::Spot {
+        return Ok(load_price(config, price_account, asset_symbol, asset_type)?.price);
+    }
+
+    require!(
+        accumulator.asset_symbol == asset_symbol && accumulator.asset_type == asset_type,
+        ErrorCode::InvalidOracleAccount
+    );
+
+    let clock = Clock::get()?;
+    if config.max_staleness_slots > 0 {
+        require!(
+            clock.slot.saturating_sub(accumulator.last_slot) <= config.max_staleness_slots,
+            ErrorCode::StalePrice
+        );
+    }
+
+    accumulated_price(accumulator, mode, clock.slot)
+}
+
+/// Fixed-width symbol encoding used by zero-copy accounts
+pub fn encode_symbol(asset_symbol: &str) -> [u8; SYMBOL_LEN] {
+    let mut encoded = [0u8; SYMBOL_LEN];
+    let bytes = asset_symbol.as_bytes();
+    let len = bytes.len().min(SYMBOL_LEN);
+    encoded[..len].copy_from_slice(&bytes[..len]);
+    encoded
+}
+
+/// Append an observation to the history ring buffer, overwriting the oldest entry when full
+pub fn record_history(
+    history: &mut PriceHistory,
+    slot: u64,
+    price: u64,
+    confidence: u64,
+    source: PriceSource,
+) {
+    let head = history.head as usize;
+    history.entries[head] = PriceHistoryEntry {
+        slot,
+        price,
+        confidence,
+        source: source as u8,
+        _padding: [0; 7],
+    };
+    history.head = ((head + 1) % PRICE_HISTORY_LEN) as u32;
+    if (history.len as usize) < PRICE_HISTORY_LEN {
+        history.len += 1;
+    }
+}
+
+/// Latest recorded entry at or before `slot`
+pub fn history_price_at(history: &PriceHistory, slot: u64) -> Result<PriceHistoryEntry> {
+    let len = history.len as usize;
+    require!(len > 0, ErrorCode::PriceHistoryUnavailable);
+
+    // Logical index 0 is the oldest entry
+    let oldest = (history.head as usize + PRICE_HISTORY_LEN - len) % PRICE_HISTORY_LEN;
+    let entry_at = |i: usize| &history.entries[(oldest + i) % PRICE_HISTORY_LEN];
+
+    require!(entry_at(0).slot <= slot, ErrorCode::PriceHistoryUnavailable);
+
+    // Entries are written in slot order, so binary search for the last slot <= target
+    let (mut lo, mut hi) = (0usize, len);
+    while hi - lo > 1 {
+        let mid = (lo + hi) / 2;
+        if entry_at(mid).slot <= slot {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+
+    Ok(*entry_at(lo))
+}
+
+/// Admin of any of the program's oracle accounts (OracleMock, MultiAssetOracle, OracleAggregator)
+pub fn oracle_admin(oracle: &AccountInfo) -> Result<Pubkey> {
+    oracle_discriminator(oracle)?;
+    let data = oracle.try_borrow_data()?;
+
+    // All oracle accounts store the admin as their first field
+    Pubkey::try_from(&data[8..40]).map_err(|_| error!(ErrorCode::InvalidOracleAccount))
+}
+
+// Discriminator of a program-owned oracle account, rejecting any other account type
+fn oracle_discriminator(oracle: &AccountInfo) -> Result<[u8; 8]> {
+    require_keys_eq!(*oracle.owner, crate::ID, ErrorCode::InvalidOracleAccount);
+    let data = oracle.try_borrow_data()?;
+    require!(data.len() >= 8 + 32, ErrorCode::InvalidOracleAccount);
+
+    let mut discriminator = [0u8; 8];
+    discriminator.copy_from_slice(&data[..8]);
+    require!(
+        discriminator == OracleMock::DISCRIMINATOR
+            || discriminator == MultiAssetOracle::DISCRIMINATOR
+            || discriminator == OracleAggregator::DISCRIMINATOR,
+        ErrorCode::InvalidOracleAccount
+    );
+    Ok(discriminator)
+}
+
+/// Check that `signer` may write prices to `oracle`: either the oracle admin,
+/// or a registered updater whose scope covers `asset`
+pub fn authorize_updater(
+    oracle: &AccountInfo,
+    signer: &Pubkey,
+    updater_role: Option<&OracleUpdater>,
+    asset: Option<(&str, AssetType)>,
+) -> Result<()> {
+    if oracle_admin(oracle)? == *signer {
+        return Ok(());
+    }
+
+    let role = updater_role.ok_or(ErrorCode::Unauthorized)?;
+    require_keys_eq!(role.oracle, oracle.key(), ErrorCode::Unauthorized);
+    require_keys_eq!(role.updater, *signer, ErrorCode::Unauthorized);
+
+    if let Some((asset_symbol, asset_type)) = asset {
+        require!(
+            role.asset_type_mask & (1 << asset_type as u8) != 0,
+            ErrorCode::UpdaterOutOfScope
+        );
+        require!(
+            role.asset_symbols.is_empty() || role.asset_symbols.iter().any(|s| s == asset_symbol),
+            ErrorCode::UpdaterOutOfScope
+        );
+    }
+    Ok(())
+}
+
+/// Per-oracle guard on how far a pushed price may move from the previous one
+#[derive(Clone, Copy, Debug)]
+pub struct MoveLimits {
+    pub max_move_bps_per_update: u16, // 0 disables the per-update check
+    pub max_move_bps_per_slot: u16,   // 0 disables the per-slot check
+    pub override_delay_secs: u64,     // Seconds before a single-authority override can execute
+}
+
+/// Reject a price that moved further from `previous_price` than the oracle's limits allow.
+/// The per-slot allowance scales with the slots elapsed since the previous update.
+/// Builds with the `no-move-limits` feature (implied by `localnet`) skip the check.
+pub fn check_price_move(
+    limits: MoveLimits,
+    previous_price: u64,
+    previous_slot: u64,
+    price: u64,
+    slot: u64,
+) -> Result<()> {
+    if cfg!(feature = "no-move-limits") || previous_price == 0 {
+        return Ok(());
+    }
+
+    let move_bps = price.abs_diff(previous_price) as u128 * 10_000 / previous_price as u128;
+    if limits.max_move_bps_per_update > 0 {
+        require!(
+            move_bps <= limits.max_move_bps_per_update as u128,
+            ErrorCode::OracleMoveTooLarge
+        );
+    }
+    if limits.max_move_bps_per_slot > 0 {
+        let elapsed_slots = slot.saturating_sub(previous_slot).max(1);
+        require!(
+            move_bps <= limits.max_move_bps_per_slot as u128 * elapsed_slots as u128,
+            ErrorCode::OracleMoveTooLarge
+        );
+    }
+    Ok(())
+}
+
+/// Write `price` into the aggregator column fed by `source` and re-aggregate. Both the
+/// column and the aggregated price it produces must stay within the aggregator's move
+/// limits. Returns the new aggregated price.
+pub fn write_feed_price(
+    feed: &mut PriceFeed,
+    source: PriceSource,
+    limits: MoveLimits,
+    price: u64,
+    slot: u64,
+    written_by: Pubkey,
+) -> Result<u64> {
+    let (mut pyth_price, mut switchboard_price, mut external_price) =
+        (feed.pyth_price, feed.switchboard_price, feed.external_price);
+    let column = match source {
+        PriceSource::Pyth => &mut pyth_price,
+        PriceSource::Switchboard => &mut switchboard_price,
+        PriceSource::CoinGecko | PriceSource::TwelveData | PriceSource::Binance => &mut external_price,
+        _ => return err!(ErrorCode::InvalidPriceSource),
+    };
+    check_price_move(limits, *column, feed.last_updated, price, slot)?;
+    *column = price;
+
+    let aggregated_price = crate::calculate_aggregated_price(pyth_price, switchboard_price, external_price);
+    check_price_move(limits, feed.aggregated_price, feed.last_updated, aggregated_price, slot)?;
+
+    feed.pyth_price = pyth_price;
+    feed.switchboard_price = switchboard_price;
+    feed.external_price = external_price;
+    feed.aggregated_price = aggregated_price;
+    feed.last_updated = slot;
+    feed.is_stale = 0;
+    feed.updated_by = written_by;
+    Ok(aggregated_price)
+}
+
+/// Move limits configured on any of the program's oracle accounts
+pub fn move_limits(oracle: &AccountInfo) -> Result<MoveLimits> {
+    let discriminator = oracle_discriminator(oracle)?;
+    if discriminator == OracleMock::DISCRIMINATOR {
+        let mock = deserialize_price_account::<OracleMock>(oracle)?;
+        Ok(MoveLimits {
+            max_move_bps_per_update: mock.max_move_bps_per_update,
+            max_move_bps_per_slot: mock.max_move_bps_per_slot,
+            override_delay_secs: mock.override_delay_secs,
+        })
+    } else if discriminator == MultiAssetOracle::DISCRIMINATOR {
+        let (header, _) = load_table::<MultiAssetOracle, AssetPrice>(oracle)?;
+        Ok(MoveLimits {
+            max_move_bps_per_update: header.max_move_bps_per_update,
+            max_move_bps_per_slot: header.max_move_bps_per_slot,
+            override_delay_secs: header.override_delay_secs,
+        })
+    } else {
+        let (header, _) = load_table::<OracleAggregator, PriceFeed>(oracle)?;
+        Ok(MoveLimits {
+            max_move_bps_per_update: header.max_move_bps_per_update,
+            max_move_bps_per_slot: header.max_move_bps_per_slot,
+            override_delay_secs: header.override_delay_secs,
+        })
+    }
+}
+
+/// Store new move limits on an oracle account
+pub fn set_move_limits(oracle: &AccountInfo, limits: MoveLimits) -> Result<()> {
+    let discriminator = oracle_discriminator(oracle)?;
+    if discriminator == OracleMock::DISCRIMINATOR {
+        let mut mock = deserialize_price_account::<OracleMock>(oracle)?;
+        mock.max_move_bps_per_update = limits.max_move_bps_per_update;
+        mock.max_move_bps_per_slot = limits.max_move_bps_per_slot;
+        mock.override_delay_secs = limits.override_delay_secs;
+        mock.try_serialize(&mut &mut oracle.try_borrow_mut_data()?[..])?;
+    } else if discriminator == MultiAssetOracle::DISCRIMINATOR {
+        let (mut header, _) = load_table_mut::<MultiAssetOracle, AssetPrice>(oracle)?;
+        header.max_move_bps_per_update = limits.max_move_bps_per_update;
+        header.max_move_bps_per_slot = limits.max_move_bps_per_slot;
+        header.override_delay_secs = limits.override_delay_secs;
+    } else {
+        let (mut header, _) = load_table_mut::<OracleAggregator, PriceFeed>(oracle)?;
+        header.max_move_bps_per_update = limits.max_move_bps_per_update;
+        header.max_move_bps_per_slot = limits.max_move_bps_per_slot;
+        header.override_delay_secs = limits.override_delay_secs;
+    }
+    Ok(())
+}
+
+/// Write an approved override price straight into an oracle account, bypassing move limits.
+/// Returns the oracle mode the write feeds and the price that mode now reads
+/// (the re-aggregated price for the aggregator).
+pub fn write_override_price(
+    oracle: &AccountInfo,
+    asset_symbol: &str,
+    asset_type: AssetType,
+    price: u64,
+    slot: u64,
+    written_by: Pubkey,
+) -> Result<(OracleMode, u64)> {
+    let discriminator = oracle_discriminator(oracle)?;
+    if discriminator == OracleMock::DISCRIMINATOR {
+        let mut mock = deserialize_price_account::<OracleMock>(oracle)?;
+        require!(
+            mock.asset_symbol == asset_symbol && mock.asset_type == asset_type,
+            ErrorCode::AssetNotFound
+        );
+        mock.price = price;
+        mock.updated_slot = slot;
+        mock.updated_by = written_by;
+        mock.try_serialize(&mut &mut oracle.try_borrow_mut_data()?[..])?;
+        Ok((OracleMode::Mock, price))
+    } else if discriminator == MultiAssetOracle::DISCRIMINATOR {
+        let (mut header, mut asset_prices) = load_table_mut::<MultiAssetOracle, AssetPrice>(oracle)?;
+        let index = find_or_insert(&mut asset_prices, &mut header.len, encode_symbol(asset_symbol), asset_type as u8)?;
+        let asset_price = &mut asset_prices[index];
+        asset_price.price = price;
+        asset_price.last_updated = slot;
+        asset_price.source = PriceSource::Manual as u8;
+        asset_price.updated_by = written_by;
+        header.updated_slot = slot;
+        Ok((OracleMode::MultiAsset, price))
+    } else {
+        let (mut header, mut price_feeds) = load_table_mut::<OracleAggregator, PriceFeed>(oracle)?;
+        let index = find_or_insert(&mut price_feeds, &mut header.len, encode_symbol(asset_symbol), asset_type as u8)?;
+        let feed = &mut price_feeds[index];
+        feed.external_price = price;
+        feed.last_updated = slot;
+        feed.is_stale = 0;
+        feed.updated_by = written_by;
+        feed.aggregated_price =
+            crate::calculate_aggregated_price(feed.pyth_price, feed.switchboard_price, feed.external_price);
+        Ok((OracleMode::Aggregated, feed.aggregated_price))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn accumulator(twap_window_slots: u64, ema_period_slots: u64) -> PriceAccumulator {
+        PriceAccumulator {
+            asset_symbol: "BTC".to_string(),
+            asset_type: AssetType::Crypto,
+            last_price: 0,
+            last_slot: 0,
+            cumulative_price: 0,
+            window_start_slot: 0,
+            window_start_cumulative: 0,
+            twap_price: 0,
+            twap_window_slots,
+            ema_period_slots,
+            ema_price: 0,
+            bump: 0,
+        }
+    }
+
+    fn history_with(slots: impl Iterator<Item = u64>) -> PriceHistory {
+        let mut history: PriceHistory = bytemuck::Zeroable::zeroed();
+        for slot in slots {
+            record_history(&mut history, slot, slot * 1_000, 0, PriceSource::Manual);
+        }
+        history
+    }
+
+    fn config(mode: OracleMode) -> OracleConfig {
+        OracleConfig {
+            admin: Pubkey::default(),
+            mode,
+            max_staleness_slots: 0,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn fold_ema_without_elapsed_slots_keeps_ema() {
+        assert_eq!(fold_ema(1_000_000, 2_000_000, 0, 150), 1_000_000);
+    }
+
+    #[test]
+    fn fold_ema_moves_one_minus_one_over_e_per_period() {
+        // (1 - 1/150)^150 ~= e^-1, so one period covers ~63.3% of the gap
+        let ema = fold_ema(1_000_000, 2_000_000, 150, 150);
+        assert!((1_630_000..1_640_000).contains(&ema), "ema {}", ema);
+    }
+
+    #[test]
+    fn fold_ema_keeps_weighting_past_one_period() {
+        // A linear blend would already have reached the new price here
+        let ema = fold_ema(1_000_000, 2_000_000, 300, 150);
+        assert!((1_860_000..1_870_000).contains(&ema), "ema {}", ema);
+        assert_eq!(fold_ema(1_000_000, 2_000_000, 100_000, 150), 2_000_000);
+    }
+
+    #[test]
+    fn fold_ema_composes_over_split_intervals() {
+        let split = fold_ema(fold_ema(1_000_000, 2_000_000, 40, 150), 2_000_000, 60, 150);
+        let whole = fold_ema(1_000_000, 2_000_000, 100, 150);
+        assert!(split.abs_diff(whole) <= 1, "split {} whole {}", split, whole);
+    }
+
+    #[test]
+    fn fold_ema_with_short_period_tracks_price() {
+        assert_eq!(fold_ema(1_000_000, 2_000_000, 1, 1), 2_000_000);
+        assert_eq!(fold_ema(1_000_000, 2_000_000, 5, 0), 2_000_000);
+    }
+
+    #[test]
+    fn record_price_weights_twap_by_time_held() {
+        let mut acc = accumulator(20, 150);
+        record_price(&mut acc, 100, 10);
+        record_price(&mut acc, 200, 20);
+        record_price(&mut acc, 300, 30);
+
+        // 100 held for 10 slots, 200 held for 10 slots
+        assert_eq!(acc.twap_price, 150);
+        assert_eq!(acc.window_start_slot, 30);
+        assert_eq!(accumulated_price(&acc, PriceMode::Spot, 30).unwrap(), 300);
+    }
+
+    #[test]
+    fn record_active_price_ignores_other_sources() {
+        let mut acc = accumulator(20, 150);
+        let aggregated = config(OracleMode::Aggregated);
+
+        record_active_price(&mut acc, &aggregated, &[(OracleMode::MultiAsset, 500)], 10);
+        assert_eq!(acc.last_slot, 0);
+
+        record_active_price(&mut acc, &aggregated, &[(OracleMode::Pyth, 400), (OracleMode::Aggregated, 450)], 10);
+        assert_eq!(acc.last_price, 450);
+        assert_eq!(acc.last_slot, 10);
+    }
+
+    #[test]
+    fn history_lookup_on_empty_history_fails() {
+        let history = history_with(std::iter::empty());
+        assert!(history_price_at(&history, 100).is_err());
+    }
+
+    #[test]
+    fn history_lookup_returns_latest_entry_at_or_before_slot() {
+        let history = history_with((1..=5).map(|i| i * 10));
+        assert_eq!(history.len, 5);
+
+        assert_eq!(history_price_at(&history, 10).unwrap().slot, 10);
+        assert_eq!(history_price_at(&history, 35).unwrap().slot, 30);
+        assert_eq!(history_price_at(&history, 35).unwrap().price, 30_000);
+        assert_eq!(history_price_at(&history, 1_000).unwrap().slot, 50);
+    }
+
+    #[test]
+    fn history_lookup_before_oldest_entry_fails() {
+        let history = history_with((1..=5).map(|i| i * 10));
+        assert!(history_price_at(&history, 9).is_err());
+    }
+
+    #[test]
+    fn history_wraps_and_overwrites_oldest_entries() {
+        let writes = PRICE_HISTORY_LEN as u64 + 10;
+        let history = history_with((0..writes).map(|i| 100 + i * 10));
+
+        assert_eq!(history.len as usize, PRICE_HISTORY_LEN);
+        assert_eq!(history.head, 10);
+
+        // The first 10 writes were overwritten, so slot 200 is now the oldest entry
+        assert!(history_price_at(&history, 195).is_err());
+        assert_eq!(history_price_at(&history, 200).unwrap().slot, 200);
+        assert_eq!(history_price_at(&history, 205).unwrap().slot, 200);
+
+        // Lookups either side of the physical end of the buffer
+        let last_physical = 100 + (PRICE_HISTORY_LEN as u64 - 1) * 10;
+        assert_eq!(history_price_at(&history, last_physical + 5).unwrap().slot, last_physical);
+        assert_eq!(history_price_at(&history, last_physical + 10).unwrap().slot, last_physical + 10);
+
+        let newest = 100 + (writes - 1) * 10;
+        assert_eq!(history_price_at(&history, newest).unwrap().price, newest * 1_000);
+        assert_eq!(history_price_at(&history, u64::MAX).unwrap().slot, newest);
+    }
+
+    fn limits(max_move_bps_per_update: u16) -> MoveLimits {
+        MoveLimits { max_move_bps_per_update, max_move_bps_per_slot: 0, override_delay_secs: 0 }
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-move-limits"))]
+    fn write_feed_price_limits_each_column() {
+        let mut feed: PriceFeed = bytemuck::Zeroable::zeroed();
+        let writer = Pubkey::new_unique();
+
+        // First writes to empty columns are unconstrained
+        assert_eq!(write_feed_price(&mut feed, PriceSource::Pyth, limits(500), 100_000, 1, writer).unwrap(), 100_000);
+        assert_eq!(
+            write_feed_price(&mut feed, PriceSource::Switchboard, limits(500), 104_000, 2, writer).unwrap(),
+            102_000
+        );
+        assert_eq!(feed.updated_by, writer);
+
+        // A Pyth jump beyond 5% is rejected and leaves the feed untouched
+        assert!(write_feed_price(&mut feed, PriceSource::Pyth, limits(500), 110_000, 3, writer).is_err());
+        assert_eq!((feed.pyth_price, feed.aggregated_price, feed.last_updated), (100_000, 102_000, 2));
+
+        assert!(write_feed_price(&mut feed, PriceSource::Manual, limits(500), 100_000, 3, writer).is_err());
+    }
+
+    #[test]
+    #[cfg(not(feature = "no-move-limits"))]
+    fn write_feed_price_limits_aggregated_price() {
+        let mut feed: PriceFeed = bytemuck::Zeroable::zeroed();
+        let writer = Pubkey::new_unique();
+        write_feed_price(&mut feed, PriceSource::Pyth, limits(500), 100_000, 1, writer).unwrap();
+
+        // A new external column is unconstrained on its own, but it would move the
+        // aggregated price (the median of two sources) by 15%
+        assert!(write_feed_price(&mut feed, PriceSource::Binance, limits(500), 130_000, 2, writer).is_err());
+        assert_eq!((feed.external_price, feed.aggregated_price), (0, 100_000));
+
+        assert_eq!(write_feed_price(&mut feed, PriceSource::Binance, limits(500), 108_000, 2, writer).unwrap(), 104_000);
+    }
+
+    #[test]
+    fn mock_only_prices_its_own_asset() {
+        let mock = OracleMock {
+            admin: Pubkey::default(),
+            price: 1_000_000,
+            updated_slot: 7,
+            updated_by: Pubkey::default(),
+            max_move_bps_per_update: 0,
+            max_move_bps_per_slot: 0,
+            override_delay_secs: 0,
+            asset_symbol: "BTC".to_string(),
+            asset_type: AssetType::Crypto,
+        };
+        assert_eq!(mock.read_price("BTC", AssetType::Crypto).unwrap().price, 1_000_000);
+        assert!(mock.read_price("ETH", AssetType::Crypto).is_err());
+        assert!(mock.read_price("BTC", AssetType::Stock).is_err());
+    }
+}
@@ -0,0 +1,336 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use bytemuck::Pod;
+use std::cell::{Ref, RefMut};
+
+use crate::{AssetPrice, ErrorCode, HeatmapRow, PriceFeed, SYMBOL_LEN};
+
+// Zero-copy oracle accounts are laid out as
+// [discriminator][header][entry; capacity]
+// and the entries form an open-addressed hash table keyed by (symbol, asset type).
+// The capacity is not stored; it is however many entries fit after the header.
+
+/// An entry slot in a zero-copy price table
+pub trait TableEntry: Pod {
+    fn symbol(&self) -> &[u8; SYMBOL_LEN];
+    fn asset_type(&self) -> u8;
+    fn is_occupied(&self) -> bool;
+    /// Claim an empty slot for a new key
+    fn occupy(&mut self, symbol: [u8; SYMBOL_LEN], asset_type: u8);
+}
+
+impl TableEntry for AssetPrice {
+    fn symbol(&self) -> &[u8; SYMBOL_LEN] {
+        &self.asset_symbol
+    }
+    fn asset_type(&self) -> u8 {
+        self.asset_type
+    }
+    fn is_occupied(&self) -> bool {
+        self.occupied != 0
+    }
+    fn occupy(&mut self, symbol: [u8; SYMBOL_LEN], asset_type: u8) {
+        self.asset_symbol = symbol;
+        self.asset_type = asset_type;
+        self.occupied = 1;
+    }
+}
+
+impl TableEntry for PriceFeed {
+    fn symbol(&self) -> &[u8; SYMBOL_LEN] {
+        &self.asset_symbol
+    }
+    fn asset_type(&self) -> u8 {
+        self.asset_type
+    }
+    fn is_occupied(&self) -> bool {
+        self.occupied != 0
+    }
+    fn occupy(&mut self, symbol: [u8; SYMBOL_LEN], asset_type: u8) {
+        self.asset_symbol = symbol;
+        self.asset_type = asset_type;
+        self.occupied = 1;
+    }
+}
+
+impl TableEntry for HeatmapRow {
+    fn symbol(&self) -> &[u8; SYMBOL_LEN] {
+        &self.asset_symbol
+    }
+    fn asset_type(&self) -> u8 {
+        self.asset_type
+    }
+    fn is_occupied(&self) -> bool {
+        self.occupied != 0
+    }
+    fn occupy(&mut self, symbol: [u8; SYMBOL_LEN], asset_type: u8) {
+        self.asset_symbol = symbol;
+        self.asset_type = asset_type;
+        self.occupied = 1;
+    }
+}
+
+/// Account size for a table with `capacity` entries
+pub fn table_space<H, E>(capacity: usize) -> usize {
+    8 + std::mem::size_of::<H>() + capacity * std::mem::size_of::<E>()
+}
+
+// FNV-1a over the symbol bytes and asset type
+fn hash_key(symbol: &[u8; SYMBOL_LEN], asset_type: u8) -> u32 {
+    let mut hash: u32 = 0x811c_9dc5;
+    for byte in symbol.iter().chain(std::iter::once(&asset_type)) {
+        hash ^= *byte as u32;
+        hash = hash.wrapping_mul(0x0100_0193);
+    }
+    hash
+}
+
+fn check_account<H: Discriminator>(info: &AccountInfo, data: &[u8]) -> Result<()> {
+    require_keys_eq!(*info.owner, crate::ID, ErrorCode::InvalidOracleAccount);
+    require!(
+        data.len() >= 8 + std::mem::size_of::<H>() && &data[..8] == H::DISCRIMINATOR,
+        ErrorCode::InvalidOracleAccount
+    );
+    Ok(())
+}
+
+/// Borrow the header and entries of a zero-copy price table
+pub fn load_table<'a, H: Pod + Discriminator, E: TableEntry>(
+    info: &'a AccountInfo,
+) -> Result<(Ref<'a, H>, Ref<'a, [E]>)> {
+    let data = info.try_borrow_data()?;
+    check_account::<H>(info, &data)?;
+
+    Ok(Ref::map_split(data, |data| {
+        let (header, entries) = data[8..].split_at(std::mem::size_of::<H>());
+        let count = entries.len() / std::mem::size_of::<E>();
+        (
+            bytemuck::from_bytes(header),
+            bytemuck::cast_slice(&entries[..count * std::mem::size_of::<E>()]),
+        )
+    }))
+}
+
+/// Mutably borrow the header and entries of a zero-copy price table
+pub fn load_table_mut<'a, H: Pod + Discriminator, E: TableEntry>(
+    info: &'a AccountInfo,
+) -> Result<(RefMut<'a, H>, RefMut<'a, [E]>)> {
+    let data = info.try_borrow_mut_data()?;
+    check_account::<H>(info, &data)?;
+
+    Ok(RefMut::map_split(data, |data| {
+        let (header, entries) = data[8..].split_at_mut(std::mem::size_of::<H>());
+        let count = entries.len() / std::mem::size_of::<E>();
+        (
+            bytemuck::from_bytes_mut(header),
+            bytemuck::cast_slice_mut(&mut entries[..count * std::mem::size_of::<E>()]),
+        )
+    }))
+}
+
+/// O(1) expected lookup of an existing entry
+pub fn find_entry<E: TableEntry>(entries: &[E], symbol: &[u8; SYMBOL_LEN], asset_type: u8) -> Option<usize> {
+    let capacity = entries.len();
+    if capacity == 0 {
+        return None;
+    }
+    let start = hash_key(symbol, asset_type) as usize % capacity;
+    for probe in 0..capacity {
+        let index = (start + probe) % capacity;
+        let entry = &entries[index];
+        if !entry.is_occupied() {
+            return None;
+        }
+        if entry.symbol() == symbol && entry.asset_type() == asset_type {
+            return Some(index);
+        }
+    }
+    None
+}
+
+/// Find an entry, claiming a new slot if the key is not present.
+/// Refuses to fill the table past 3/4 so probe sequences stay short.
+pub fn find_or_insert<E: TableEntry>(
+    entries: &mut [E],
+    len: &mut u32,
+    symbol: [u8; SYMBOL_LEN],
+    asset_type: u8,
+) -> Result<usize> {
+    let capacity = entries.len();
+    require!(capacity > 0, ErrorCode::OracleCapacityExceeded);
+
+    let start = hash_key(&symbol, asset_type) as usize % capacity;
+    for probe in 0..capacity {
+        let index = (start + probe) % capacity;
+        let entry = &mut entries[index];
+        if !entry.is_occupied() {
+            require!(
+                (*len as usize + 1) * 4 <= capacity * 3,
+                ErrorCode::OracleCapacityExceeded
+            );
+            entry.occupy(symbol, asset_type);
+            *len += 1;
+            return Ok(index);
+        }
+        if *entry.symbol() == symbol && entry.asset_type() == asset_type {
+            return Ok(index);
+        }
+    }
+    err!(ErrorCode::OracleCapacityExceeded)
+}
+
+/// Reallocate a table account to `new_capacity` entries and rehash its contents.
+/// The payer covers the extra rent.
+pub fn grow_table<'info, H: Pod + Discriminator, E: TableEntry>(
+    info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    new_capacity: usize,
+) -> Result<()> {
+    let existing: Vec<E> = {
+        let (_, entries) = load_table::<H, E>(info)?;
+        require!(new_capacity > entries.len(), ErrorCode::InvalidOracleCapacity);
+        entries.iter().filter(|e| e.is_occupied()).copied().collect()
+    };
+
+    let new_len = table_space::<H, E>(new_capacity);
+    let rent_due = Rent::get()?.minimum_balance(new_len).saturating_sub(info.lamports());
+    if rent_due > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                anchor_lang::system_program::Transfer {
+                    from: payer.clone(),
+                    to: info.clone(),
+                },
+            ),
+            rent_due,
+        )?;
+    }
+    info.resize(new_len)?;
+
+    let (_, mut entries) = load_table_mut::<H, E>(info)?;
+    rehash_entries(&existing, &mut entries)?;
+    Ok(())
+}
+
+// Clear `entries` and re-insert `existing` at their new hash positions, returning the new len
+fn rehash_entries<E: TableEntry>(existing: &[E], entries: &mut [E]) -> Result<u32> {
+    for entry in entries.iter_mut() {
+        *entry = E::zeroed();
+    }
+    let mut len = 0u32;
+    for old in existing {
+        let index = find_or_insert(entries, &mut len, *old.symbol(), old.asset_type())?;
+        entries[index] = *old;
+    }
+    Ok(len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::oracle::encode_symbol;
+
+    fn table(capacity: usize) -> Vec<AssetPrice> {
+        vec![bytemuck::Zeroable::zeroed(); capacity]
+    }
+
+    fn start_index(symbol: &str, capacity: usize) -> usize {
+        hash_key(&encode_symbol(symbol), 0) as usize % capacity
+    }
+
+    // First symbol "A0", "A1", ... whose probe sequence starts at `index`
+    fn symbol_starting_at(index: usize, capacity: usize, skip: usize) -> String {
+        (0..)
+            .map(|i| format!("A{}", i))
+            .filter(|symbol| start_index(symbol, capacity) == index)
+            .nth(skip)
+            .unwrap()
+    }
+
+    #[test]
+    fn colliding_keys_probe_to_the_next_slot() {
+        let mut entries = table(8);
+        let mut len = 0;
+        let first = symbol_starting_at(3, 8, 0);
+        let second = symbol_starting_at(3, 8, 1);
+
+        let first_index = find_or_insert(&mut entries, &mut len, encode_symbol(&first), 0).unwrap();
+        let second_index = find_or_insert(&mut entries, &mut len, encode_symbol(&second), 0).unwrap();
+
+        assert_eq!((first_index, second_index), (3, 4));
+        assert_eq!(len, 2);
+        assert_eq!(find_entry(&entries, &encode_symbol(&first), 0), Some(3));
+        assert_eq!(find_entry(&entries, &encode_symbol(&second), 0), Some(4));
+        // Same symbol under another asset type is a different key
+        assert_eq!(find_entry(&entries, &encode_symbol(&first), 1), None);
+    }
+
+    #[test]
+    fn probing_wraps_past_the_end_of_the_table() {
+        let mut entries = table(8);
+        let mut len = 0;
+        let first = symbol_starting_at(7, 8, 0);
+        let second = symbol_starting_at(7, 8, 1);
+
+        find_or_insert(&mut entries, &mut len, encode_symbol(&first), 0).unwrap();
+        let wrapped = find_or_insert(&mut entries, &mut len, encode_symbol(&second), 0).unwrap();
+
+        assert_eq!(wrapped, 0);
+        assert_eq!(find_entry(&entries, &encode_symbol(&second), 0), Some(0));
+    }
+
+    #[test]
+    fn inserts_stop_at_three_quarters_load() {
+        let mut entries = table(8);
+        let mut len = 0;
+        for i in 0..6 {
+            find_or_insert(&mut entries, &mut len, encode_symbol(&format!("S{}", i)), 0).unwrap();
+        }
+        assert_eq!(len, 6);
+
+        assert!(find_or_insert(&mut entries, &mut len, encode_symbol("S6"), 0).is_err());
+        assert_eq!(len, 6);
+
+        // Existing keys are still found and updatable at full load
+        let index = find_or_insert(&mut entries, &mut len, encode_symbol("S2"), 0).unwrap();
+        assert_eq!(find_entry(&entries, &encode_symbol("S2"), 0), Some(index));
+        assert_eq!(len, 6);
+    }
+
+    #[test]
+    fn missing_key_is_not_found() {
+        let mut entries = table(8);
+        let mut len = 0;
+        find_or_insert(&mut entries, &mut len, encode_symbol("BTC"), 1).unwrap();
+        assert_eq!(find_entry(&entries, &encode_symbol("ETH"), 1), None);
+        assert_eq!(find_entry(&table(0), &encode_symbol("ETH"), 1), None);
+    }
+
+    #[test]
+    fn rehash_preserves_entries_when_growing() {
+        let mut entries = table(8);
+        let mut len = 0;
+        for (i, symbol) in ["BTC", "ETH", "SOL", "AAPL", "GOLD", "TSLA"].iter().enumerate() {
+            let index = find_or_insert(&mut entries, &mut len, encode_symbol(symbol), 1).unwrap();
+            entries[index].price = 1_000 * (i as u64 + 1);
+        }
+        let existing: Vec<AssetPrice> = entries.iter().filter(|e| e.is_occupied()).copied().collect();
+
+        let mut grown = table(32);
+        assert_eq!(rehash_entries(&existing, &mut grown).unwrap(), 6);
+
+        for (i, symbol) in ["BTC", "ETH", "SOL", "AAPL", "GOLD", "TSLA"].iter().enumerate() {
+            let index = find_entry(&grown, &encode_symbol(symbol), 1).unwrap();
+            assert_eq!(grown[index].price, 1_000 * (i as u64 + 1));
+        }
+
+        // The grown table accepts the inserts the old one refused
+        let mut len = 6;
+        for i in 0..18 {
+            find_or_insert(&mut grown, &mut len, encode_symbol(&format!("N{}", i)), 0).unwrap();
+        }
+        assert_eq!(len, 24);
+    }
+}
@@ -0,0 +1,246 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token_2022::spl_token_2022::instruction::AuthorityType;
+use anchor_spl::token_interface::spl_pod::optional_keys::OptionalNonZeroPubkey;
+use anchor_spl::token_interface::spl_token_metadata_interface::state::{Field, TokenMetadata};
+use anchor_spl::token_interface::{
+    self, Burn, CloseAccount, MintTo, SetAuthority, TokenMetadataInitialize, TokenMetadataUpdateField,
+    TransferChecked,
+};
+
+use crate::SettlementSlot;
+
+// Settlement slots are supply-1 Token-2022 mints. The metadata-pointer extension points
+// at the mint itself and the token-metadata extension stores the slot's terms, so
+// wallets and marketplaces can display and transfer slots without reading our accounts.
+// The SettlementSlot PDA is the update authority, permanent delegate and close authority
+// of its mint, so an expired slot's token can be burned and its mint closed without the holder.
+
+/// Token symbol shown by wallets for settlement slot NFTs
+pub const SLOT_NFT_SYMBOL: &str = "CSLOT";
+
+/// Display name, e.g. "AAPL T+0 Settlement Slot"
+pub fn slot_name(slot: &SettlementSlot) -> String {
+    format!("{} T+{} Settlement Slot", slot.asset_symbol, slot.settlement_time)
+}
+
+/// Additional metadata key/value pairs describing the slot's terms
+pub fn slot_attributes(slot: &SettlementSlot) -> Vec<(String, String)> {
+    vec![
+        ("asset".to_string(), slot.asset_symbol.clone()),
+        ("asset_type".to_string(), format!("{:?}", slot.asset_type)),
+        ("tenor".to_string(), format!("T+{}", slot.settlement_time)),
+        ("expiry".to_string(), slot.expiry_timestamp.to_string()),
+        ("slot_id".to_string(), slot.slot_id.to_string()),
+    ]
+}
+
+/// Write the slot's token metadata onto its mint. The mint is topped up from `payer`
+/// first, since Token-2022 reallocates the mint as metadata is added but does not fund it.
+pub fn init_slot_metadata<'info>(
+    token_program: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    slot_authority: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    slot: &SettlementSlot,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let metadata = TokenMetadata {
+        update_authority: OptionalNonZeroPubkey(slot_authority.key()),
+        mint: mint.key(),
+        name: slot_name(slot),
+        symbol: SLOT_NFT_SYMBOL.to_string(),
+        uri: String::new(),
+        additional_metadata: slot_attributes(slot),
+    };
+    let required = Rent::get()?.minimum_balance(mint.data_len() + metadata.tlv_size_of()?);
+    let shortfall = required.saturating_sub(mint.lamports());
+    if shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                system_program::Transfer { from: payer.clone(), to: mint.clone() },
+            ),
+            shortfall,
+        )?;
+    }
+
+    token_interface::token_metadata_initialize(
+        CpiContext::new_with_signer(
+            token_program.clone(),
+            TokenMetadataInitialize {
+                program_id: token_program.clone(),
+                metadata: mint.clone(),
+                update_authority: slot_authority.clone(),
+                mint_authority: slot_authority.clone(),
+                mint: mint.clone(),
+            },
+            signer_seeds,
+        ),
+        metadata.name,
+        metadata.symbol,
+        metadata.uri,
+    )?;
+
+    for (key, value) in metadata.additional_metadata {
+        update_slot_attribute(token_program, mint, slot_authority, signer_seeds, key, value)?;
+    }
+    Ok(())
+}
+
+/// Set one additional metadata field on a slot mint
+pub fn update_slot_attribute<'info>(
+    token_program: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    slot_authority: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    key: String,
+    value: String,
+) -> Result<()> {
+    update_metadata_field(token_program, mint, slot_authority, signer_seeds, Field::Key(key), value)
+}
+
+/// Rewrite the name and tenor after a slot's settlement time changes. Both only ever
+/// shrink or keep their length when moving to a shorter tenor, so no top-up is needed.
+pub fn refresh_slot_tenor<'info>(
+    token_program: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    slot_authority: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    slot: &SettlementSlot,
+) -> Result<()> {
+    update_metadata_field(token_program, mint, slot_authority, signer_seeds, Field::Name, slot_name(slot))?;
+    update_slot_attribute(
+        token_program,
+        mint,
+        slot_authority,
+        signer_seeds,
+        "tenor".to_string(),
+        format!("T+{}", slot.settlement_time),
+    )
+}
+
+fn update_metadata_field<'info>(
+    token_program: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    slot_authority: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    field: Field,
+    value: String,
+) -> Result<()> {
+    token_interface::token_metadata_update_field(
+        CpiContext::new_with_signer(
+            token_program.clone(),
+            TokenMetadataUpdateField {
+                program_id: token_program.clone(),
+                metadata: mint.clone(),
+                update_authority: slot_authority.clone(),
+            },
+            signer_seeds,
+        ),
+        field,
+        value,
+    )
+}
+
+/// Mint the single slot token to `holder` and drop the mint authority so supply stays at 1
+pub fn mint_slot_token<'info>(
+    token_program: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    holder: &AccountInfo<'info>,
+    slot_authority: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    token_interface::mint_to(
+        CpiContext::new_with_signer(
+            token_program.clone(),
+            MintTo {
+                mint: mint.clone(),
+                to: holder.clone(),
+                authority: slot_authority.clone(),
+            },
+            signer_seeds,
+        ),
+        1,
+    )?;
+
+    token_interface::set_authority(
+        CpiContext::new_with_signer(
+            token_program.clone(),
+            SetAuthority {
+                current_authority: slot_authority.clone(),
+                account_or_mint: mint.clone(),
+            },
+            signer_seeds,
+        ),
+        AuthorityType::MintTokens,
+        None,
+    )
+}
+
+/// Move the slot token between holders; `signer_seeds` is empty when `authority` signed the transaction
+pub fn transfer_slot_token<'info>(
+    token_program: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    from: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            token_program.clone(),
+            TransferChecked {
+                from: from.clone(),
+                mint: mint.clone(),
+                to: to.clone(),
+                authority: authority.clone(),
+            },
+            signer_seeds,
+        ),
+        1,
+        0,
+    )
+}
+
+/// Burn the slot token from whoever holds it, signed by the SettlementSlot as permanent delegate
+pub fn burn_slot_token<'info>(
+    token_program: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    holder: &AccountInfo<'info>,
+    slot_authority: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    token_interface::burn(
+        CpiContext::new_with_signer(
+            token_program.clone(),
+            Burn {
+                mint: mint.clone(),
+                from: holder.clone(),
+                authority: slot_authority.clone(),
+            },
+            signer_seeds,
+        ),
+        1,
+    )
+}
+
+/// Close a slot mint once its token is burned, signed by the SettlementSlot as close authority
+pub fn close_slot_mint<'info>(
+    token_program: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    slot_authority: &AccountInfo<'info>,
+    rent_to: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    token_interface::close_account(CpiContext::new_with_signer(
+        token_program.clone(),
+        CloseAccount {
+            account: mint.clone(),
+            destination: rent_to.clone(),
+            authority: slot_authority.clone(),
+        },
+        signer_seeds,
+    ))
+}
@@ -33,12 +33,22 @@ async function fullTest() {
         console.log("\n🎯 TEST 1: Settlement Slot NFT Minting");
         console.log("-".repeat(50));
 
-        const currentSlot = await program.provider.connection.getSlot();
+        // Slots are numbered by a global counter; the next id is 0 until the counter exists
+        const [slotCounterPDA] = PublicKey.findProgramAddressSync(
+            [Buffer.from("slot_counter")],
+            program.programId
+        );
+        let nextSlotId = new anchor.BN(0);
+        try {
+            const counter = await program.account.slotCounter.fetch(slotCounterPDA);
+            nextSlotId = counter.nextSlotId;
+        } catch (err) {
+            // First mint creates the counter
+        }
         const [settlementSlotPDA] = PublicKey.findProgramAddressSync(
             [
                 Buffer.from("settlement_slot"),
-                wallet.publicKey.toBuffer(),
-                new anchor.BN(currentSlot).toArrayLike(Buffer, 'le', 8)
+                nextSlotId.toArrayLike(Buffer, 'le', 8)
             ],
             program.programId
         );
@@ -50,9 +60,10 @@ async function fullTest() {
                     { crypto: {} },
                     new anchor.BN(0), // T+0
                     new anchor.BN(30), // 30 days
-                    new anchor.BN(50_000_000) // $50
+                    new anchor.BN(50_000_000) // pay at most $50
                 )
                 .accounts({
+                    slotCounter: slotCounterPDA,
                     settlementSlot: settlementSlotPDA,
                     owner: wallet.publicKey,
                     systemProgram: SystemProgram.programId,
@@ -138,7 +149,12 @@ async function fullTest() {
         console.log("-".repeat(50));
 
         const [poolPDA] = PublicKey.findProgramAddressSync(
-            [Buffer.from("settlement_pool"), wallet.publicKey.toBuffer()],
+            [
+                Buffer.from("settlement_pool"),
+                Buffer.from("BTC"),
+                Buffer.from([1]), // AssetType::Crypto
+                new anchor.BN(0).toArrayLike(Buffer, "le", 8), // T+0
+            ],
             program.programId
         );
 
@@ -68,7 +68,7 @@ async function initMarketNow() {
         if (!oracleMockAccount) {
             console.log('🔧 Initializing oracle mock first...');
             const initOracleTx = await program.methods
-                .initOracle()
+                .initOracle("BTC", { crypto: {} })
                 .accounts({
                     oracleMock: ORACLE_MOCK_PDA,
                     admin: wallet.publicKey,
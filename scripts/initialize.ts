@@ -152,55 +152,45 @@ async function initialize() {
         for (const asset of cryptoAssets) {
             try {
                 const pythPriceAccount = new PublicKey(asset.pythAccount);
-                const tx = await program.methods
-                    .updatePriceFromPyth(
+
+                // Pyth pulls are only accepted from the account pinned for the asset
+                await program.methods
+                    .setPriceFeedAccounts(
                         asset.symbol,
-                        { crypto: {} }
+                        { crypto: {} },
+                        pythPriceAccount,
+                        PublicKey.default
                     )
                     .accounts({
                         oracleAggregator: oracleAggregatorPDA,
-                        pythPriceAccount: pythPriceAccount,
-                        user: wallet.publicKey,
+                        governance: null,
+                        authority: wallet.publicKey,
                     })
                     .rpc();
 
-                console.log(`✅ ${asset.symbol} REAL Pyth price updated: ${tx.slice(0, 8)}...`);
-            } catch (error: any) {
-                console.log(`⚠️ Failed to update ${asset.symbol} from Pyth:`, error.message);
-            }
-        }
-
-        // Update stock/commodity prices from external (manual for demo)
-        console.log("\n📈 Adding stock/commodity prices (external source)...");
-        const externalAssets = [
-            { symbol: "AAPL", type: { stock: {} }, price: 178 },
-            { symbol: "TSLA", type: { stock: {} }, price: 242 },
-            { symbol: "GOOGL", type: { stock: {} }, price: 140 },
-            { symbol: "GOLD", type: { commodity: {} }, price: 2040 },
-        ];
-
-        for (const asset of externalAssets) {
-            try {
-                const priceInDecimals = Math.floor(asset.price * 1_000_000);
                 const tx = await program.methods
-                    .updatePriceFromExternal(
+                    .updatePriceFromPyth(
                         asset.symbol,
-                        asset.type,
-                        new anchor.BN(priceInDecimals),
-                        { coinGecko: {} }
+                        { crypto: {} }
                     )
                     .accounts({
                         oracleAggregator: oracleAggregatorPDA,
-                        admin: wallet.publicKey,
+                        pythPriceAccount: pythPriceAccount,
+                        updaterRole: null,
+                        updater: wallet.publicKey,
                     })
                     .rpc();
 
-                console.log(`✅ ${asset.symbol} external price updated: $${asset.price}`);
+                console.log(`✅ ${asset.symbol} REAL Pyth price updated: ${tx.slice(0, 8)}...`);
             } catch (error: any) {
-                console.log(`⚠️ Failed to update ${asset.symbol}:`, error.message);
+                console.log(`⚠️ Failed to update ${asset.symbol} from Pyth:`, error.message);
             }
         }
 
+        // Stock/commodity prices reach the aggregator as Ed25519-signed attestations from
+        // registered publishers (submitPriceAttestation), relayed by the publisher service
+        console.log("\n📈 Stock/commodity prices arrive via signed publisher attestations");
+
         // ========================================
         // TASK 9: Initialize Settlement Slot Pool
         // ========================================
@@ -209,20 +199,61 @@ async function initialize() {
         // Using existing devnet USDC mint
         console.log("✅ Using Devnet USDC Mint:", usdcMint.toString());
 
-        // Create settlement slot NFT mint (mock)
-        console.log("Creating settlement slot NFT mint...");
-        const slotNftMint = await createMint(
-            provider.connection,
-            wallet.payer,
-            wallet.publicKey,
-            wallet.publicKey,
-            0 // NFTs have 0 decimals
+        // The pool's token B is the BTC T+0 tenor token, minted one-for-one against wrapped slots
+        console.log("Creating BTC T+0 tenor vault...");
+        const [tenorVaultPDA] = PublicKey.findProgramAddressSync(
+            [
+                Buffer.from("tenor_vault"),
+                Buffer.from("BTC"),
+                Buffer.from([1]), // AssetType::Crypto
+                new anchor.BN(0).toArrayLike(Buffer, "le", 8), // T+0
+            ],
+            program.programId
+        );
+        const [tenorMint] = PublicKey.findProgramAddressSync(
+            [Buffer.from("tenor_mint"), tenorVaultPDA.toBuffer()],
+            program.programId
         );
-        console.log("✅ Settlement Slot NFT Mint:", slotNftMint.toString());
+
+        try {
+            const tx = await program.methods
+                .createTenorVault("BTC", { crypto: {} }, new anchor.BN(0))
+                .accounts({
+                    tenorVault: tenorVaultPDA,
+                    tenorMint: tenorMint,
+                    payer: wallet.publicKey,
+                    tokenProgram: TOKEN_PROGRAM_ID,
+                    systemProgram: SystemProgram.programId,
+                })
+                .rpc();
+            console.log("✅ Tenor vault created:", tx);
+        } catch (err: any) {
+            if (err.message.includes("already in use")) {
+                console.log("⚠️ Tenor vault already initialized");
+            } else {
+                console.log("⚠️ Tenor vault creation failed:", err.message);
+            }
+        }
+        console.log("✅ Tenor Token Mint:", tenorMint.toString());
 
         // Find pool PDA
         const [poolPDA] = PublicKey.findProgramAddressSync(
-            [Buffer.from("settlement_pool"), wallet.publicKey.toBuffer()],
+            [
+                Buffer.from("settlement_pool"),
+                Buffer.from("BTC"),
+                Buffer.from([1]), // AssetType::Crypto
+                new anchor.BN(0).toArrayLike(Buffer, "le", 8), // T+0
+            ],
+            program.programId
+        );
+
+        const [lpMintPDA] = PublicKey.findProgramAddressSync(
+            [Buffer.from("pool_lp_mint"), poolPDA.toBuffer()],
+            program.programId
+        );
+
+        const [poolRegistryPDA] = PublicKey.findProgramAddressSync(
+            [Buffer.from("pool_registry")],
             program.programId
         );
 
@@ -254,9 +285,14 @@ async function initialize() {
                 .accounts({
                     pool: poolPDA,
                     usdcMint: usdcMint,
-                    slotNftMint: slotNftMint,
+                    tenorVault: tenorVaultPDA,
+                    tenorMint: tenorMint,
                     tokenAVault: tokenAVaultPDA,
                     tokenBVault: tokenBVaultPDA,
+                    lpMint: lpMintPDA,
+                    poolRegistry: poolRegistryPDA,
+                    governance: governancePDA,
+                    protocolFeeVault: protocolFeeVaultPDA,
                     poolAuthority: poolAuthorityPDA,
                     creator: wallet.publicKey,
                     tokenProgram: TOKEN_PROGRAM_ID,
@@ -286,7 +322,7 @@ async function initialize() {
             governance: governancePDA.toString(),
             oracleAggregator: oracleAggregatorPDA.toString(),
             mockUsdcMint: usdcMint.toString(),
-            slotNftMint: slotNftMint.toString(),
+            tenorMint: tenorMint.toString(),
             settlementPool: poolPDA.toString(),
             timestamp: new Date().toISOString(),
         };
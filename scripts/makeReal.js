@@ -86,7 +86,7 @@ async function main() {
         // Initialize oracle
         console.log('🔮 Initializing Oracle...');
         const tx = await program.methods
-            .initOracle()
+            .initOracle("BTC", { crypto: {} })
             .accounts({
                 oracleMock: oraclePDA,
                 admin: walletKeypair.publicKey,
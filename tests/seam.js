@@ -1,6 +1,19 @@
 const anchor = require("@coral-xyz/anchor");
 const { PublicKey, Keypair } = require("@solana/web3.js");
-const { TOKEN_PROGRAM_ID, createMint, createAccount, mintTo } = require("@solana/spl-token");
+const {
+  TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  createMint,
+  createAccount,
+  mintTo,
+  transfer,
+  getAccount,
+  getMint,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+} = require("@solana/spl-token");
+const { assert } = require("chai");
 
 describe("seam", () => {
   // Configure the client to use the local cluster.
@@ -74,7 +87,7 @@ describe("seam", () => {
       program.programId
     );
 
-    const tx = await program.methods.initOracle().accounts({
+    const tx = await program.methods.initOracle("BTC", { crypto: {} }).accounts({
       oracleMock: oraclePda,
       admin: provider.wallet.publicKey,
       systemProgram: anchor.web3.SystemProgram.programId,
@@ -83,21 +96,59 @@ describe("seam", () => {
     console.log("Init oracle transaction signature:", tx);
   });
 
+  it("Initializes oracle config", async () => {
+    const [oracleConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("oracle_config")],
+      program.programId
+    );
+
+    // Mock mode requires a program built with `--features localnet`
+    const tx = await program.methods.initOracleConfig({ mock: {} }, new anchor.BN(0)).accounts({
+      oracleConfig: oracleConfigPda,
+      admin: provider.wallet.publicKey,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    }).rpc();
+
+    console.log("Init oracle config transaction signature:", tx);
+  });
+
   it("Updates oracle price", async () => {
     const [oraclePda] = PublicKey.findProgramAddressSync(
       [Buffer.from("oracle")],
       program.programId
     );
 
-    const [marketPda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("market")],
+    const [oracleConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("oracle_config")],
       program.programId
     );
 
-    const tx = await program.methods.updateOracle(new anchor.BN(1000000)).accounts({
-      oracleMock: oraclePda,
-      market: marketPda,
+    const [accumulatorPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("price_accumulator"), Buffer.from("BTC"), Buffer.from([1])],
+      program.programId
+    );
+
+    const [historyPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("price_history"), Buffer.from("BTC"), Buffer.from([1])],
+      program.programId
+    );
+
+    // Every price write is recorded, so the asset's history must exist first
+    await program.methods.initPriceHistory("BTC", { crypto: {} }).accounts({
+      oracleConfig: oracleConfigPda,
+      priceHistory: historyPda,
       admin: provider.wallet.publicKey,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    }).rpc();
+
+    const tx = await program.methods.updateOracle("BTC", { crypto: {} }, new anchor.BN(1000000)).accounts({
+      oracleMock: oraclePda,
+      oracleConfig: oracleConfigPda,
+      updaterRole: null,
+      priceAccumulator: accumulatorPda,
+      priceHistory: historyPda,
+      updater: provider.wallet.publicKey,
+      systemProgram: anchor.web3.SystemProgram.programId,
     }).rpc();
 
     console.log("Update oracle transaction signature:", tx);
@@ -142,12 +193,34 @@ describe("seam", () => {
       program.programId
     );
 
-    const tx = await program.methods.initMarket().accounts({
+    const [oracleConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("oracle_config")],
+      program.programId
+    );
+
+    const tx = await program.methods.initMarket(
+      "BTC",
+      { crypto: {} },
+      { spot: {} }, // entry price mode
+      { spot: {} }, // liquidation price mode
+      { spot: {} }, // settlement price mode
+      new anchor.BN(2) // settles T+2 business days
+    ).accounts({
       market: marketPda,
       user: provider.wallet.publicKey,
       usdcVault: usdcVaultPda,
       usdcMint: usdcMint,
-      oracleMock: oraclePda,
+      oracleConfig: oracleConfigPda,
+      priceAccount: oraclePda,
+      priceAccumulator: PublicKey.findProgramAddressSync(
+        [Buffer.from("price_accumulator"), Buffer.from("BTC"), Buffer.from([1])],
+        program.programId
+      )[0],
+      // Always passed; crypto has no schedule configured, so the PDA is empty
+      tradingSchedule: PublicKey.findProgramAddressSync(
+        [Buffer.from("trading_schedule"), Buffer.from("BTC"), Buffer.from([1])],
+        program.programId
+      )[0],
       tokenProgram: TOKEN_PROGRAM_ID,
       systemProgram: anchor.web3.SystemProgram.programId,
       rent: anchor.web3.SYSVAR_RENT_PUBKEY,
@@ -182,6 +255,11 @@ describe("seam", () => {
       program.programId
     );
 
+    const [oracleConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("oracle_config")],
+      program.programId
+    );
+
     // Create user CFD account
     userCfdAccount = await createAccount(
       provider.connection,
@@ -202,7 +280,8 @@ describe("seam", () => {
       userUsdcAccount: userUsdcAccount,
       usdcMint: usdcMint,
       user: provider.wallet.publicKey,
-      oracleMock: oraclePda,
+      oracleConfig: oracleConfigPda,
+      priceAccount: oraclePda,
       tokenProgram: TOKEN_PROGRAM_ID,
       associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
       systemProgram: anchor.web3.SystemProgram.programId,
@@ -237,6 +316,11 @@ describe("seam", () => {
       program.programId
     );
 
+    const [oracleConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("oracle_config")],
+      program.programId
+    );
+
     const tx = await program.methods.settleMarket().accounts({
       market: marketPda,
       position: positionPda,
@@ -246,7 +330,8 @@ describe("seam", () => {
       userUsdcAccount: userUsdcAccount,
       usdcMint: usdcMint,
       user: provider.wallet.publicKey,
-      oracleMock: oraclePda,
+      oracleConfig: oracleConfigPda,
+      priceAccount: oraclePda,
       tokenProgram: TOKEN_PROGRAM_ID,
       associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
       systemProgram: anchor.web3.SystemProgram.programId,
@@ -254,4 +339,745 @@ describe("seam", () => {
 
     console.log("Settle market transaction signature:", tx);
   });
+
+  describe("settlement slots", () => {
+    const USDC = 10 ** 6;
+    const BASE_PRICE = 1 * USDC;
+    const PRICE_STEP = USDC / 2;
+    const NOTIONAL_BUDGET = 20 * USDC; // two minimum-size bets
+    const BET_AMOUNT = 10 * USDC;
+
+    const wallet = provider.wallet.publicKey;
+    const other = Keypair.generate();
+    let otherUsdcAccount;
+    let betSeed = 0;
+
+    const pda = (...seeds) => PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+    const u64 = (n) => new anchor.BN(n).toArrayLike(Buffer, "le", 8);
+
+    const governancePda = pda(Buffer.from("governance"));
+    const feeVaultPda = pda(Buffer.from("protocol_fee_vault"));
+    const slotCounterPda = pda(Buffer.from("slot_counter"));
+    const oraclePda = pda(Buffer.from("oracle"));
+    const oracleConfigPda = pda(Buffer.from("oracle_config"));
+    const schedulePda = pda(Buffer.from("trading_schedule"), Buffer.from("BTC"), Buffer.from([1]));
+    const pricingPda = (tenor) => pda(Buffer.from("slot_pricing"), Buffer.from("BTC"), Buffer.from([1]), u64(tenor));
+    const slotPda = (id) => pda(Buffer.from("settlement_slot"), u64(id));
+    const slotMintPda = (slot) => pda(Buffer.from("slot_mint"), slot.toBuffer());
+    const slotAta = (mint, owner) => getAssociatedTokenAddressSync(mint, owner, true, TOKEN_2022_PROGRAM_ID);
+    const balance = async (account) => Number((await getAccount(provider.connection, account)).amount);
+
+    async function expectError(promise, code) {
+      try {
+        await promise;
+      } catch (err) {
+        assert.equal(err.error?.errorCode?.code, code, err.toString());
+        return;
+      }
+      assert.fail(`expected ${code}`);
+    }
+
+    async function nextSlotId() {
+      const counter = await program.account.slotCounter.fetchNullable(slotCounterPda);
+      return counter ? counter.nextSlotId.toNumber() : 0;
+    }
+
+    // Mints a BTC slot off the tenor's curve to the wallet
+    async function mintSlot({ tenor = 0, slotDuration = 7 } = {}) {
+      const id = await nextSlotId();
+      const slot = slotPda(id);
+      const mint = slotMintPda(slot);
+      await program.methods.mintSettlementSlotNft(
+        "BTC",
+        { crypto: {} },
+        new anchor.BN(tenor),
+        new anchor.BN(slotDuration),
+        new anchor.BN(100 * USDC)
+      ).accounts({
+        slotPricing: pricingPda(tenor),
+        governance: governancePda,
+        protocolFeeVault: feeVaultPda,
+        ownerUsdcAccount: userUsdcAccount,
+        slotCounter: slotCounterPda,
+        settlementSlot: slot,
+        slotMint: mint,
+        ownerTokenAccount: slotAta(mint, wallet),
+        tradingSchedule: schedulePda,
+        owner: wallet,
+        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        token2022Program: TOKEN_2022_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }).rpc();
+      return { id, slot, mint, tenor };
+    }
+
+    async function placeBet(user, amount = BET_AMOUNT) {
+      const seed = betSeed++;
+      const bet = pda(Buffer.from("bet"), user.publicKey.toBuffer(), u64(seed));
+      await program.methods.placeBet("BTC", { crypto: {} }, new anchor.BN(amount), true, new anchor.BN(seed)).accounts({
+        bet,
+        oracleConfig: oracleConfigPda,
+        priceAccount: oraclePda,
+        tradingSchedule: schedulePda,
+        user: user.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }).signers(user === provider.wallet ? [] : [user]).rpc();
+      return { seed, bet };
+    }
+
+    // Settles a bet against a slot; escrow accounts are passed only when the slot sits in one
+    function settleWithSlot(user, bet, slot, slotTokenAccount, escrow = {}) {
+      return program.methods.instantT0Settlement(new anchor.BN(bet.seed), new anchor.BN(slot.id)).accounts({
+        bet: bet.bet,
+        settlementSlot: slot.slot,
+        slotTokenAccount,
+        slotRental: escrow.slotRental ?? null,
+        fractionVault: escrow.fractionVault ?? null,
+        fractionMint: escrow.fractionMint ?? null,
+        userFractionAccount: escrow.userFractionAccount ?? null,
+        tokenProgram: escrow.fractionVault ? TOKEN_PROGRAM_ID : null,
+        oracleConfig: oracleConfigPda,
+        priceAccount: oraclePda,
+        tradingSchedule: schedulePda,
+        user: user.publicKey,
+      }).signers(user === provider.wallet ? [] : [user]).rpc();
+    }
+
+    function expireSlot(slot, holder, rentRecipient, cranker = provider.wallet) {
+      return program.methods.expireSettlementSlot().accounts({
+        settlementSlot: slot.slot,
+        slotPricing: pricingPda(slot.tenor),
+        slotMint: slot.mint,
+        holderTokenAccount: slotAta(slot.mint, holder),
+        holder,
+        rentRecipient,
+        cranker: cranker.publicKey,
+        token2022Program: TOKEN_2022_PROGRAM_ID,
+      }).signers(cranker === provider.wallet ? [] : [cranker]).rpc({ commitment: "confirmed" });
+    }
+
+    // Puts a wallet-held slot up for rent at `feePerDay` USDC
+    async function listForRent(slot, feePerDay, maxDays = 3) {
+      const rental = pda(Buffer.from("slot_rental"), slot.slot.toBuffer());
+      await program.methods.listSlotForRent(new anchor.BN(feePerDay), new anchor.BN(maxDays)).accounts({
+        settlementSlot: slot.slot,
+        rental,
+        slotMint: slot.mint,
+        lenderTokenAccount: slotAta(slot.mint, wallet),
+        escrowTokenAccount: slotAta(slot.mint, rental),
+        governance: governancePda,
+        protocolFeeVault: feeVaultPda,
+        lenderUsdcAccount: userUsdcAccount,
+        lender: wallet,
+        token2022Program: TOKEN_2022_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }).rpc();
+      return rental;
+    }
+
+    // Locks a wallet-held slot in its fraction vault and mints the fractions to the wallet
+    async function fractionalize(slot, totalFractions) {
+      const vault = pda(Buffer.from("fraction_vault"), slot.slot.toBuffer());
+      const fractionMint = pda(Buffer.from("fraction_mint"), slot.slot.toBuffer());
+      const ownerFractions = getAssociatedTokenAddressSync(fractionMint, wallet);
+      await program.methods.fractionalizeSlot(new anchor.BN(totalFractions)).accounts({
+        settlementSlot: slot.slot,
+        fractionVault: vault,
+        fractionMint,
+        slotMint: slot.mint,
+        ownerTokenAccount: slotAta(slot.mint, wallet),
+        escrowTokenAccount: slotAta(slot.mint, vault),
+        ownerFractionAccount: ownerFractions,
+        owner: wallet,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        token2022Program: TOKEN_2022_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }).rpc();
+      return { vault, fractionMint, ownerFractions };
+    }
+
+    function redeemSlot(slot, redeemer, redeemerFractionAccount) {
+      const vault = pda(Buffer.from("fraction_vault"), slot.slot.toBuffer());
+      return program.methods.redeemSlot().accounts({
+        fractionVault: vault,
+        settlementSlot: slot.slot,
+        fractionMint: pda(Buffer.from("fraction_mint"), slot.slot.toBuffer()),
+        redeemerFractionAccount,
+        owner: wallet,
+        slotMint: slot.mint,
+        escrowTokenAccount: slotAta(slot.mint, vault),
+        redeemerTokenAccount: slotAta(slot.mint, redeemer.publicKey),
+        redeemer: redeemer.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        token2022Program: TOKEN_2022_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }).signers(redeemer === provider.wallet ? [] : [redeemer]).rpc({ commitment: "confirmed" });
+    }
+
+    const fractionAccount = async (fractionMint, owner) =>
+      (await getOrCreateAssociatedTokenAccount(provider.connection, provider.wallet.payer, fractionMint, owner)).address;
+
+    const lamports = async (account) => (await provider.connection.getAccountInfo(account))?.lamports ?? 0;
+
+    before(async () => {
+      const cadenMint = await createMint(provider.connection, provider.wallet.payer, wallet, null, 6);
+      await program.methods.initGovernance().accounts({
+        governance: governancePda,
+        cadenMint,
+        stakedCadenMint: pda(Buffer.from("staked_caden_mint")),
+        protocolFeeVault: feeVaultPda,
+        buybackVault: pda(Buffer.from("buyback_vault")),
+        usdcMint,
+        admin: wallet,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      }).rpc();
+
+      // T+0 slots are capped and carry a notional budget; T+1 slots are not
+      for (const [tenor, maxSupply, notionalBudget] of [[0, 20, NOTIONAL_BUDGET], [1, 0, 0]]) {
+        await program.methods.setSlotPricing("BTC", { crypto: {} }, new anchor.BN(tenor), {
+          basePrice: new anchor.BN(BASE_PRICE),
+          priceStep: new anchor.BN(PRICE_STEP),
+          maxSupply: new anchor.BN(maxSupply),
+          notionalBudget: new anchor.BN(notionalBudget),
+        }).accounts({
+          slotPricing: pricingPda(tenor),
+          governance: governancePda,
+          admin: wallet,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        }).rpc();
+      }
+
+      // A second user to trade, rent and hold fractions
+      await provider.connection.confirmTransaction(
+        await provider.connection.requestAirdrop(other.publicKey, 2 * anchor.web3.LAMPORTS_PER_SOL),
+        "confirmed"
+      );
+      otherUsdcAccount = await createAccount(provider.connection, provider.wallet.payer, usdcMint, other.publicKey);
+      await mintTo(provider.connection, provider.wallet.payer, usdcMint, otherUsdcAccount, wallet, 100 * USDC);
+    });
+
+    it("Charges the curve price for each minted slot", async () => {
+      const pricingBefore = await program.account.slotPricing.fetch(pricingPda(0));
+      const vaultBefore = await balance(feeVaultPda);
+
+      const first = await mintSlot();
+      const second = await mintSlot();
+
+      const outstanding = pricingBefore.outstanding.toNumber();
+      const firstPrice = BASE_PRICE + PRICE_STEP * outstanding;
+      const secondPrice = BASE_PRICE + PRICE_STEP * (outstanding + 1);
+      assert.equal(await balance(feeVaultPda), vaultBefore + firstPrice + secondPrice);
+
+      const slot = await program.account.settlementSlot.fetch(second.slot);
+      assert.equal(slot.mintPrice.toNumber(), secondPrice);
+      assert.equal(slot.notionalBudget.toNumber(), NOTIONAL_BUDGET);
+      assert.equal(await balance(slotAta(first.mint, wallet)), 1);
+
+      const pricing = await program.account.slotPricing.fetch(pricingPda(0));
+      assert.equal(pricing.outstanding.toNumber(), outstanding + 2);
+    });
+
+    it("Refuses to sell a slot that would already be expired", async () => {
+      const vaultBefore = await balance(feeVaultPda);
+      await expectError(mintSlot({ slotDuration: 0 }), "InvalidSlotDuration");
+      await expectError(mintSlot({ slotDuration: 366 }), "InvalidSlotDuration");
+      assert.equal(await balance(feeVaultPda), vaultBefore);
+    });
+
+    // Lists a slot held by `seller` for `price` USDC
+    function listSlot(slot, seller, price) {
+      const listing = pda(Buffer.from("slot_listing"), slot.slot.toBuffer());
+      return program.methods.listSettlementSlot(new anchor.BN(price)).accounts({
+        settlementSlot: slot.slot,
+        listing,
+        slotMint: slot.mint,
+        sellerTokenAccount: slotAta(slot.mint, seller.publicKey),
+        escrowTokenAccount: slotAta(slot.mint, listing),
+        seller: seller.publicKey,
+        token2022Program: TOKEN_2022_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }).signers(seller === provider.wallet ? [] : [seller]).rpc();
+    }
+
+    function cancelListing(slot, seller) {
+      const listing = pda(Buffer.from("slot_listing"), slot.slot.toBuffer());
+      return program.methods.cancelSlotListing().accounts({
+        listing,
+        escrowTokenAccount: slotAta(slot.mint, listing),
+        slotMint: slot.mint,
+        settlementSlot: slot.slot,
+        sellerTokenAccount: slotAta(slot.mint, seller.publicKey),
+        seller: seller.publicKey,
+        token2022Program: TOKEN_2022_PROGRAM_ID,
+      }).signers(seller === provider.wallet ? [] : [seller]).rpc();
+    }
+
+    // Buys a slot listed by the wallet on behalf of the second user
+    function buyListedSlot(slot, price) {
+      const listing = pda(Buffer.from("slot_listing"), slot.slot.toBuffer());
+      return program.methods.buySettlementSlot(new anchor.BN(price)).accounts({
+        settlementSlot: slot.slot,
+        listing,
+        slotMint: slot.mint,
+        escrowTokenAccount: slotAta(slot.mint, listing),
+        buyerTokenAccount: slotAta(slot.mint, other.publicKey),
+        governance: governancePda,
+        protocolFeeVault: feeVaultPda,
+        buyerUsdcAccount: otherUsdcAccount,
+        sellerUsdcAccount: userUsdcAccount,
+        seller: wallet,
+        buyer: other.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        token2022Program: TOKEN_2022_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }).signers([other]).rpc();
+    }
+
+    it("Lists, cancels and sells a slot through escrow", async () => {
+      const slot = await mintSlot();
+      const listing = pda(Buffer.from("slot_listing"), slot.slot.toBuffer());
+      const escrow = slotAta(slot.mint, listing);
+      const price = 5 * USDC;
+
+      await listSlot(slot, provider.wallet, price);
+      assert.equal(await balance(escrow), 1);
+
+      // Only the seller may pull the slot back out of escrow
+      await expectError(
+        program.methods.cancelSlotListing().accounts({
+          listing,
+          escrowTokenAccount: escrow,
+          slotMint: slot.mint,
+          settlementSlot: slot.slot,
+          sellerTokenAccount: slotAta(slot.mint, wallet),
+          seller: other.publicKey,
+          token2022Program: TOKEN_2022_PROGRAM_ID,
+        }).signers([other]).rpc(),
+        "ConstraintHasOne"
+      );
+
+      await cancelListing(slot, provider.wallet);
+      assert.equal(await balance(slotAta(slot.mint, wallet)), 1);
+      assert.isNull(await program.account.slotListing.fetchNullable(listing));
+
+      await listSlot(slot, provider.wallet, price);
+      const governance = await program.account.cadenGovernance.fetch(governancePda);
+      const royalty = Math.floor((price * governance.slotRoyaltyBps) / 10000);
+      const sellerBefore = await balance(userUsdcAccount);
+      const buyerBefore = await balance(otherUsdcAccount);
+
+      // The buyer's price bound is enforced
+      await expectError(buyListedSlot(slot, price - 1), "SlippageExceeded");
+      await buyListedSlot(slot, price);
+
+      assert.equal(await balance(slotAta(slot.mint, other.publicKey)), 1);
+      assert.equal(await balance(otherUsdcAccount), buyerBefore - price);
+      assert.equal(await balance(userUsdcAccount), sellerBefore + price - royalty);
+      assert.isNull(await program.account.slotListing.fetchNullable(listing));
+    });
+
+    it("Keeps a slot addressable by its id after it changes hands", async () => {
+      const { id, mint } = await mintSlot();
+      await listSlot({ slot: slotPda(id), mint }, provider.wallet, USDC);
+      await buyListedSlot({ slot: slotPda(id), mint }, USDC);
+
+      // The PDA depends on the id alone, so the new holder finds the same account
+      const slot = slotPda(id);
+      const state = await program.account.settlementSlot.fetch(slot);
+      assert.equal(state.slotId.toNumber(), id);
+      assert.isTrue(state.creator.equals(wallet));
+      assert.isTrue(state.mint.equals(slotMintPda(slot)));
+      assert.equal(await balance(slotAta(mint, other.publicKey)), 1);
+
+      // ...and can trade it on from there
+      await listSlot({ slot, mint }, other, USDC);
+      await cancelListing({ slot, mint }, other);
+      assert.equal(await balance(slotAta(mint, other.publicKey)), 1);
+    });
+
+    it("Sells auction lots on a decaying price outside the curve", async () => {
+      const startPrice = 10 * USDC;
+      const floorPrice = 2 * USDC;
+      const decayPerSecond = USDC / 100;
+      const auction = pda(Buffer.from("slot_auction"), Buffer.from("BTC"), Buffer.from([1]), u64(0));
+      const createAuction = (epoch, slotDuration, startTimestamp) =>
+        program.methods.createSlotAuction("BTC", { crypto: {} }, new anchor.BN(epoch), {
+          totalSlots: new anchor.BN(1),
+          slotDuration: new anchor.BN(slotDuration),
+          startPrice: new anchor.BN(startPrice),
+          floorPrice: new anchor.BN(floorPrice),
+          decayPerSecond: new anchor.BN(decayPerSecond),
+          startTimestamp: new anchor.BN(startTimestamp),
+          endTimestamp: new anchor.BN(startTimestamp + 86_400),
+          notionalBudget: new anchor.BN(NOTIONAL_BUDGET),
+        }).accounts({
+          auction: pda(Buffer.from("slot_auction"), Buffer.from("BTC"), Buffer.from([1]), u64(epoch)),
+          slotPricing: pricingPda(0),
+          governance: governancePda,
+          admin: wallet,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        }).rpc();
+
+      // Opened 100 seconds ago, so the price has already decayed by the time we buy
+      const now = await provider.connection.getBlockTime(await provider.connection.getSlot());
+      const startTimestamp = now - 100;
+
+      await expectError(createAuction(1, 0, startTimestamp), "InvalidSlotDuration");
+
+      const pricingBefore = await program.account.slotPricing.fetch(pricingPda(0));
+      await createAuction(0, 7, startTimestamp);
+
+      // The lot is reserved against the cap without moving the curve price
+      let pricing = await program.account.slotPricing.fetch(pricingPda(0));
+      assert.equal(pricing.reserved.toNumber(), pricingBefore.reserved.toNumber() + 1);
+      assert.equal(pricing.outstanding.toNumber(), pricingBefore.outstanding.toNumber());
+
+      const id = await nextSlotId();
+      const slot = slotPda(id);
+      const mint = slotMintPda(slot);
+      const vaultBefore = await balance(feeVaultPda);
+      const sig = await program.methods.buyAuctionSlot(new anchor.BN(startPrice)).accounts({
+        auction,
+        slotPricing: pricingPda(0),
+        slotCounter: slotCounterPda,
+        settlementSlot: slot,
+        slotMint: mint,
+        buyerTokenAccount: slotAta(mint, wallet),
+        tradingSchedule: schedulePda,
+        governance: governancePda,
+        protocolFeeVault: feeVaultPda,
+        buyerUsdcAccount: userUsdcAccount,
+        buyer: wallet,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        token2022Program: TOKEN_2022_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }).rpc({ commitment: "confirmed" });
+
+      const tx = await provider.connection.getTransaction(sig, { commitment: "confirmed", maxSupportedTransactionVersion: 0 });
+      const expected = Math.max(floorPrice, startPrice - decayPerSecond * (tx.blockTime - startTimestamp));
+      assert.isBelow(expected, startPrice);
+      assert.equal(await balance(feeVaultPda), vaultBefore + expected);
+      assert.equal((await program.account.settlementSlot.fetch(slot)).mintPrice.toNumber(), expected);
+      assert.equal(await balance(slotAta(mint, wallet)), 1);
+
+      pricing = await program.account.slotPricing.fetch(pricingPda(0));
+      assert.equal(pricing.reserved.toNumber(), pricingBefore.reserved.toNumber());
+      assert.equal(pricing.outstanding.toNumber(), pricingBefore.outstanding.toNumber() + 1);
+
+      // Sold out, so the auction can be finalized before its end time with nothing left to release
+      await program.methods.finalizeSlotAuction().accounts({
+        auction,
+        slotPricing: pricingPda(0),
+      }).rpc();
+      assert.isTrue((await program.account.slotAuction.fetch(auction)).isFinalized);
+      pricing = await program.account.slotPricing.fetch(pricingPda(0));
+      assert.equal(pricing.reserved.toNumber(), pricingBefore.reserved.toNumber());
+    });
+
+    it("Spends a slot's notional budget and deactivates it once used up", async () => {
+      const slot = await mintSlot();
+      const tokenAccount = slotAta(slot.mint, wallet);
+
+      // Someone who does not hold the slot cannot settle with it
+      await expectError(settleWithSlot(other, await placeBet(other), slot, tokenAccount), "Unauthorized");
+
+      await settleWithSlot(provider.wallet, await placeBet(provider.wallet), slot, tokenAccount);
+      // 10 + 15 USDC would overrun the 20 USDC budget
+      await expectError(
+        settleWithSlot(provider.wallet, await placeBet(provider.wallet, 15 * USDC), slot, tokenAccount),
+        "SlotBudgetExhausted"
+      );
+      await settleWithSlot(provider.wallet, await placeBet(provider.wallet), slot, tokenAccount);
+
+      const state = await program.account.settlementSlot.fetch(slot.slot);
+      assert.equal(state.notionalUsed.toNumber(), NOTIONAL_BUDGET);
+      assert.isFalse(state.isActive);
+      await expectError(
+        settleWithSlot(provider.wallet, await placeBet(provider.wallet), slot, tokenAccount),
+        "SlotExpired"
+      );
+    });
+
+    it("Sweeps an exhausted slot, closing its mint and refunding the holder", async () => {
+      const slot = await mintSlot();
+      const tokenAccount = slotAta(slot.mint, wallet);
+
+      // A live slot cannot be swept
+      await expectError(expireSlot(slot, wallet, wallet, other), "SlotNotExpired");
+
+      await settleWithSlot(provider.wallet, await placeBet(provider.wallet), slot, tokenAccount);
+      await settleWithSlot(provider.wallet, await placeBet(provider.wallet), slot, tokenAccount);
+
+      // The rent belongs to the holder, not the cranker
+      await expectError(expireSlot(slot, wallet, other.publicKey, other), "Unauthorized");
+
+      const outstandingBefore = (await program.account.slotPricing.fetch(pricingPda(0))).outstanding.toNumber();
+      const rent = (await lamports(slot.slot)) + (await lamports(slot.mint));
+      const walletBefore = await lamports(wallet);
+      const sig = await expireSlot(slot, wallet, wallet, other);
+      // The wallet still pays the transaction fee
+      const { meta } = await provider.connection.getTransaction(sig, { commitment: "confirmed", maxSupportedTransactionVersion: 0 });
+
+      assert.isNull(await program.account.settlementSlot.fetchNullable(slot.slot));
+      assert.isNull(await provider.connection.getAccountInfo(slot.mint));
+      assert.equal(await lamports(wallet), walletBefore + rent - meta.fee);
+      const pricing = await program.account.slotPricing.fetch(pricingPda(0));
+      assert.equal(pricing.outstanding.toNumber(), outstandingBefore - 1);
+    });
+
+    it("Sweeps a slot out of a rental escrow to the lender", async () => {
+      const slot = await mintSlot();
+      const rental = await listForRent(slot, USDC);
+      const escrow = slotAta(slot.mint, rental);
+
+      // Not rented out, so the lender can still use it
+      await settleWithSlot(provider.wallet, await placeBet(provider.wallet), slot, escrow, { slotRental: rental });
+      await settleWithSlot(provider.wallet, await placeBet(provider.wallet), slot, escrow, { slotRental: rental });
+
+      await expectError(expireSlot(slot, rental, other.publicKey), "Unauthorized");
+      await expireSlot(slot, rental, wallet);
+      assert.isNull(await program.account.slotRental.fetchNullable(rental));
+      assert.isNull(await provider.connection.getAccountInfo(escrow));
+      assert.isNull(await program.account.settlementSlot.fetchNullable(slot.slot));
+    });
+
+    it("Sweeps a slot out of a fraction vault to the vault owner", async () => {
+      const slot = await mintSlot();
+      const { vault, fractionMint, ownerFractions } = await fractionalize(slot, 2);
+      const escrow = slotAta(slot.mint, vault);
+      const fractions = { fractionVault: vault, fractionMint, userFractionAccount: ownerFractions };
+
+      await settleWithSlot(provider.wallet, await placeBet(provider.wallet), slot, escrow, fractions);
+      await settleWithSlot(provider.wallet, await placeBet(provider.wallet), slot, escrow, fractions);
+
+      await expectError(expireSlot(slot, vault, other.publicKey), "Unauthorized");
+      await expireSlot(slot, vault, wallet);
+      assert.isNull(await program.account.fractionVault.fetchNullable(vault));
+      assert.isNull(await provider.connection.getAccountInfo(escrow));
+      assert.isNull(await program.account.settlementSlot.fetchNullable(slot.slot));
+    });
+
+    it("Rents out T+0 slots only, and lets only the renter use one during a rental", async () => {
+      // Only T+0 slots can be used instantly, so only they can be rented out
+      const tPlusOne = await mintSlot({ tenor: 1 });
+      await expectError(listForRent(tPlusOne, USDC), "NotInstantSettlement");
+
+      const slot = await mintSlot();
+      const feePerDay = USDC;
+      const rental = await listForRent(slot, feePerDay);
+      const escrow = slotAta(slot.mint, rental);
+
+      const lenderBefore = await balance(userUsdcAccount);
+      const renterBefore = await balance(otherUsdcAccount);
+      await program.methods.rentSettlementSlot(new anchor.BN(1), new anchor.BN(feePerDay)).accounts({
+        rental,
+        settlementSlot: slot.slot,
+        lenderUsdcAccount: userUsdcAccount,
+        renterUsdcAccount: otherUsdcAccount,
+        renter: other.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      }).signers([other]).rpc();
+      assert.equal(await balance(userUsdcAccount), lenderBefore + feePerDay);
+      assert.equal(await balance(otherUsdcAccount), renterBefore - feePerDay);
+
+      // The lender is locked out until the rental runs out
+      await expectError(
+        settleWithSlot(provider.wallet, await placeBet(provider.wallet), slot, escrow, { slotRental: rental }),
+        "Unauthorized"
+      );
+      // The rental must be passed for the escrow to count as one
+      await expectError(settleWithSlot(other, await placeBet(other), slot, escrow), "Unauthorized");
+
+      await settleWithSlot(other, await placeBet(other), slot, escrow, { slotRental: rental });
+      assert.equal((await program.account.settlementSlot.fetch(slot.slot)).notionalUsed.toNumber(), BET_AMOUNT);
+    });
+
+    it("Burns fractions to use a fractionalized slot", async () => {
+      const slot = await mintSlot();
+      const { vault, fractionMint, ownerFractions } = await fractionalize(slot, 4);
+      const escrow = slotAta(slot.mint, vault);
+      assert.equal((await program.account.fractionVault.fetch(vault)).notionalPerFraction.toNumber(), NOTIONAL_BUDGET / 4);
+
+      // Hand half the fractions to the second user
+      const otherFractions = await fractionAccount(fractionMint, other.publicKey);
+      await transfer(provider.connection, provider.wallet.payer, ownerFractions, otherFractions, wallet, 2);
+
+      // Holding fewer than every outstanding fraction is not enough to redeem
+      await expectError(redeemSlot(slot, provider.wallet, ownerFractions), "FractionsOutstanding");
+
+      const fractions = (userFractionAccount) => ({ fractionVault: vault, fractionMint, userFractionAccount });
+
+      // Without the fraction accounts the vault escrow is not a holder anyone can use
+      await expectError(settleWithSlot(other, await placeBet(other), slot, escrow), "Unauthorized");
+
+      // A 10 USDC bet burns two 5 USDC fractions
+      await settleWithSlot(other, await placeBet(other), slot, escrow, fractions(otherFractions));
+      assert.equal(await balance(otherFractions), 0);
+      assert.equal((await program.account.fractionVault.fetch(vault)).fractionsBurned.toNumber(), 2);
+
+      // Out of fractions, the second user can no longer settle with the slot
+      try {
+        await settleWithSlot(other, await placeBet(other), slot, escrow, fractions(otherFractions));
+        assert.fail("expected the fraction burn to fail");
+      } catch (err) {
+        assert.include((err.logs ?? []).join("\n"), "insufficient funds");
+      }
+
+      // The wallet now holds every outstanding fraction and can reassemble the slot
+      await redeemSlot(slot, provider.wallet, ownerFractions);
+      assert.equal(await balance(slotAta(slot.mint, wallet)), 1);
+      assert.equal(Number((await getMint(provider.connection, fractionMint)).supply), 0);
+      assert.isNull(await program.account.fractionVault.fetchNullable(vault));
+    });
+
+    it("Only lets the vault owner redeem a slot whose fractions are all burned", async () => {
+      const slot = await mintSlot();
+      const { vault, fractionMint, ownerFractions } = await fractionalize(slot, 2);
+      const escrow = slotAta(slot.mint, vault);
+
+      // A 10.5 USDC bet burns both 10 USDC fractions but leaves 9.5 USDC of budget
+      await settleWithSlot(provider.wallet, await placeBet(provider.wallet, 10.5 * USDC), slot, escrow, {
+        fractionVault: vault,
+        fractionMint,
+        userFractionAccount: ownerFractions,
+      });
+      assert.equal(Number((await getMint(provider.connection, fractionMint)).supply), 0);
+      const state = await program.account.settlementSlot.fetch(slot.slot);
+      assert.isTrue(state.isActive);
+
+      // An empty fraction account matches the zero supply, but a stranger still cannot take the slot
+      const strangerFractions = await fractionAccount(fractionMint, other.publicKey);
+      await expectError(redeemSlot(slot, other, strangerFractions), "Unauthorized");
+
+      const vaultRent = await lamports(vault);
+      const ownerBefore = await lamports(wallet);
+      const sig = await redeemSlot(slot, provider.wallet, ownerFractions);
+      const { meta } = await provider.connection.getTransaction(sig, { commitment: "confirmed", maxSupportedTransactionVersion: 0 });
+      assert.equal(await balance(slotAta(slot.mint, wallet)), 1);
+      assert.isNull(await program.account.fractionVault.fetchNullable(vault));
+      assert.equal(await lamports(wallet), ownerBefore + vaultRent - meta.fee);
+    });
+
+    it("Upgrades a T+1 slot to T+0 for the difference in curve prices", async () => {
+      const upgrade = (slot, maxPremium) =>
+        program.methods.upgradeSettlementSlot(new anchor.BN(maxPremium)).accounts({
+          settlementSlot: slot.slot,
+          tnPricing: pricingPda(1),
+          t0Pricing: pricingPda(0),
+          slotMint: slot.mint,
+          ownerTokenAccount: slotAta(slot.mint, wallet),
+          governance: governancePda,
+          protocolFeeVault: feeVaultPda,
+          ownerUsdcAccount: userUsdcAccount,
+          owner: wallet,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          token2022Program: TOKEN_2022_PROGRAM_ID,
+        }).rpc();
+
+      // A T+0 slot has nothing to upgrade to
+      await expectError(upgrade(await mintSlot(), 100 * USDC), "InvalidSettlementTime");
+
+      const slot = await mintSlot({ tenor: 1 });
+      const t0Before = await program.account.slotPricing.fetch(pricingPda(0));
+      const t1Before = await program.account.slotPricing.fetch(pricingPda(1));
+      const curvePrice = (pricing) => pricing.basePrice.toNumber() + pricing.priceStep.toNumber() * pricing.outstanding.toNumber();
+      const premium = curvePrice(t0Before) - curvePrice(t1Before);
+      assert.isAbove(premium, 0);
+
+      await expectError(upgrade(slot, premium - 1), "SlippageExceeded");
+
+      const vaultBefore = await balance(feeVaultPda);
+      await upgrade(slot, premium);
+      assert.equal(await balance(feeVaultPda), vaultBefore + premium);
+
+      const state = await program.account.settlementSlot.fetch(slot.slot);
+      assert.equal(state.settlementTime.toNumber(), 0);
+      assert.equal(state.notionalBudget.toNumber(), NOTIONAL_BUDGET);
+      assert.equal(state.notionalUsed.toNumber(), 0);
+      const t0 = await program.account.slotPricing.fetch(pricingPda(0));
+      const t1 = await program.account.slotPricing.fetch(pricingPda(1));
+      assert.equal(t0.outstanding.toNumber(), t0Before.outstanding.toNumber() + 1);
+      assert.equal(t1.outstanding.toNumber(), t1Before.outstanding.toNumber() - 1);
+
+      // The upgraded slot settles bets instantly
+      await settleWithSlot(provider.wallet, await placeBet(provider.wallet), slot, slotAta(slot.mint, wallet));
+    });
+
+    it("Backs the tenor token one-for-one with wrapped slots", async () => {
+      const tenorVault = pda(Buffer.from("tenor_vault"), Buffer.from("BTC"), Buffer.from([1]), u64(1));
+      const tenorMint = pda(Buffer.from("tenor_mint"), tenorVault.toBuffer());
+      await program.methods.createTenorVault("BTC", { crypto: {} }, new anchor.BN(1)).accounts({
+        tenorVault,
+        tenorMint,
+        payer: wallet,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }).rpc();
+
+      const wrap = (slot) =>
+        program.methods.wrapSettlementSlot().accounts({
+          settlementSlot: slot.slot,
+          tenorVault,
+          tenorMint,
+          slotMint: slot.mint,
+          ownerTokenAccount: slotAta(slot.mint, wallet),
+          escrowTokenAccount: slotAta(slot.mint, tenorVault),
+          ownerTenorAccount: getAssociatedTokenAddressSync(tenorMint, wallet),
+          owner: wallet,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          token2022Program: TOKEN_2022_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        }).rpc();
+      const unwrap = (slot, holder, holderTenorAccount) =>
+        program.methods.unwrapSettlementSlot().accounts({
+          settlementSlot: slot.slot,
+          tenorVault,
+          tenorMint,
+          holderTenorAccount,
+          slotMint: slot.mint,
+          escrowTokenAccount: slotAta(slot.mint, tenorVault),
+          holderTokenAccount: slotAta(slot.mint, holder.publicKey),
+          holder: holder.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          token2022Program: TOKEN_2022_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        }).signers(holder === provider.wallet ? [] : [holder]).rpc();
+
+      const first = await mintSlot({ tenor: 1 });
+      const second = await mintSlot({ tenor: 1 });
+      await wrap(first);
+      await wrap(second);
+      const walletTenor = getAssociatedTokenAddressSync(tenorMint, wallet);
+      assert.equal(await balance(walletTenor), 2);
+      assert.equal(Number((await getMint(provider.connection, tenorMint)).supply), 2);
+      assert.equal((await program.account.tenorVault.fetch(tenorVault)).wrapped.toNumber(), 2);
+
+      // A wrapped slot cannot be swept out from under its tenor token
+      await expectError(expireSlot(first, tenorVault, wallet), "SlotWrapped");
+
+      // Tenor tokens are fungible: another holder can unwrap either wrapped slot
+      const otherTenor = await fractionAccount(tenorMint, other.publicKey);
+      await transfer(provider.connection, provider.wallet.payer, walletTenor, otherTenor, wallet, 1);
+      await unwrap(first, other, otherTenor);
+      assert.equal(await balance(slotAta(first.mint, other.publicKey)), 1);
+      assert.equal(await balance(otherTenor), 0);
+
+      await unwrap(second, provider.wallet, walletTenor);
+      assert.equal(await balance(slotAta(second.mint, wallet)), 1);
+      assert.equal(Number((await getMint(provider.connection, tenorMint)).supply), 0);
+      assert.equal((await program.account.tenorVault.fetch(tenorVault)).wrapped.toNumber(), 0);
+    });
+  });
 });
\ No newline at end of file